@@ -1,8 +1,9 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-use crate::genome::Genome;
+use crate::genome::{self, Genome, NodeGene, NodeType};
+use crate::neat::Config;
 
-use super::{Connection, Node};
+use super::{Connection, Float, Node, NodeTrace};
 
 /// Used to calculate the outputs of a genome
 ///
@@ -25,15 +26,25 @@ use super::{Connection, Node};
 ///
 /// calc.calculate(&vec![0.0, 1.0]);
 /// ```
-#[derive(Clone, PartialEq, Eq, Debug)]
-pub struct Calculator {
-    input_nodes: Vec<Rc<RefCell<Node>>>,
-    hidden_nodes: Vec<Rc<RefCell<Node>>>,
-    output_nodes: Vec<Rc<RefCell<Node>>>,
+///
+/// Connections that form a cycle, which evolution only creates with [`Config::allow_recurrent`], are left out.
+/// Use a [`Ctrnn`](super::Ctrnn) or [`SpikingNetwork`](super::SpikingNetwork) for recurrent networks.
+///
+/// It calculates with `f32` by default, use [`Calculator::from_genome`] to calculate with another [`Float`] like `f64`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Calculator<F: Float = f32> {
+    input_nodes: Vec<Rc<RefCell<Node<F>>>>,
+    hidden_nodes: Vec<Rc<RefCell<Node<F>>>>,
+    output_nodes: Vec<Rc<RefCell<Node<F>>>>,
+    /// The innovation numbers of the input, hidden and output nodes, in that order
+    innovation_numbers: Vec<u32>,
+    plasticity: bool,
 }
 
+impl Eq for Calculator<f32> {}
+
 impl Calculator {
-    /// Create a new calculator from a genome
+    /// Create a new calculator from a genome that calculates with `f32`
     /// # Examples
     /// ```rust
     /// use profqu_neat::Neat;
@@ -47,35 +58,60 @@ impl Calculator {
     /// let calc = Calculator::new(genome);
     /// ```
     pub fn new(genome: Genome) -> Self {
+        Self::from_genome(genome)
+    }
+}
+
+impl<F: Float> Calculator<F> {
+    /// Create a new calculator from a genome that calculates with any [`Float`]
+    /// # Examples
+    /// ```rust
+    /// use profqu_neat::Neat;
+    /// use profqu_neat::calculations::Calculator;
+    ///
+    /// Neat::test_config();
+    ///
+    /// let mut neat = Neat::new(2, 1, 10);
+    /// let genome = neat.empty_genome();
+    ///
+    /// let mut calc = Calculator::<f64>::from_genome(genome);
+    /// assert_eq!(calc.calculate(&vec![0.0, 1.0]).unwrap(), vec![0.5]);
+    /// ```
+    pub fn from_genome(genome: Genome) -> Self {
         let mut calc = Self {
             input_nodes: Vec::new(),
             hidden_nodes: Vec::new(),
             output_nodes: Vec::new(),
+            innovation_numbers: Vec::new(),
+            plasticity: Config::global().plasticity,
         };
 
-        let nodes = genome.nodes;
-        let connections = genome.connections;
+        let (input_genes, bias, hidden_genes, output_genes) = genome.evaluation_order();
+        let recurrent = genome.recurrent_connections();
 
         let mut node_hash_map = HashMap::new();
-
-        for node_gene in nodes.data {
-            let node = Node::new(node_gene.x);
-            let pointer = Rc::new(RefCell::new(node));
-
+        let innovation_numbers = &mut calc.innovation_numbers;
+        let mut create_node = |node_gene: NodeGene| {
+            let pointer = Rc::new(RefCell::new(Node::new(node_gene.x)));
             node_hash_map.insert(node_gene.innovation_number, Rc::clone(&pointer));
+            innovation_numbers.push(node_gene.innovation_number);
+            pointer
+        };
 
-            if node_gene.x <= 0.1 {
-                calc.input_nodes.push(pointer);
-            } else if node_gene.x >= 0.9 {
-                calc.output_nodes.push(pointer);
-            } else {
-                calc.hidden_nodes.push(pointer);
+        // The bias node is always the last input node
+        calc.input_nodes = input_genes
+            .into_iter()
+            .chain(bias)
+            .map(&mut create_node)
+            .collect();
+        calc.hidden_nodes = hidden_genes.into_iter().map(&mut create_node).collect();
+        calc.output_nodes = output_genes.into_iter().map(&mut create_node).collect();
+
+        for connection_gene in genome.connections.data {
+            if recurrent.contains(&connection_gene.innovation_number) {
+                continue;
             }
-        }
-
-        calc.hidden_nodes.sort();
 
-        for connection_gene in connections.data {
             let from = connection_gene.from;
             let to = connection_gene.to;
 
@@ -87,8 +123,10 @@ impl Calculator {
                 .expect("'to' is not in the hashmap");
 
             let mut connection = Connection::new(Rc::clone(node_from));
-            connection.weight = connection_gene.weight;
+            connection.weight = F::from_f32(connection_gene.weight);
+            connection.initial_weight = connection.weight;
             connection.enabled = connection_gene.enabled;
+            connection.hebbian = connection_gene.hebbian;
             let pointer = Rc::new(RefCell::new(connection));
 
             node_to.borrow_mut().connections.push(pointer);
@@ -128,39 +166,250 @@ impl Calculator {
     /// # Errors
     ///
     /// Returns an error when the number of inputs aren't equal to the number of input nodes.
-    pub fn calculate(&mut self, inputs: &Vec<f32>) -> Result<Vec<f32>, &'static str> {
+    pub fn calculate(&mut self, inputs: &Vec<F>) -> Result<Vec<F>, &'static str> {
         if inputs.len() + 1 != self.input_nodes.len() {
             return Err("Number of inputs aren't equal to number of input nodes");
         }
 
         for (i, input) in inputs.iter().enumerate() {
-            self.input_nodes[i].borrow_mut().output = *input;
+            let mut node = self.input_nodes[i].borrow_mut();
+            node.sum = *input;
+            node.output = *input;
         }
 
-        self.input_nodes
+        let mut bias = self
+            .input_nodes
             .last()
             .expect("No input_nodes")
-            .borrow_mut()
-            .output = 1.0;
+            .borrow_mut();
+        bias.sum = F::ONE;
+        bias.output = F::ONE;
+        drop(bias);
 
         for hidden_node in self.hidden_nodes.clone() {
             hidden_node.borrow_mut().calculate();
         }
 
-        let mut outputs = vec![0.0; self.output_nodes.len()];
+        let mut outputs = vec![F::ZERO; self.output_nodes.len()];
 
         for (i, output) in self.output_nodes.iter().enumerate() {
             output.borrow_mut().calculate();
             outputs[i] = output.borrow().output;
         }
 
+        if self.plasticity {
+            self.update_weights();
+        }
+
         Ok(outputs)
     }
+
+    /// Create a [Graphviz](https://graphviz.org) graph of the network that is calculated, in the DOT language
+    ///
+    /// It's drawn like [`Genome::to_dot`], but only with the enabled connections and with the current weights,
+    /// which differ from the genome's weights after learning with plasticity.
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    /// use profqu_neat::calculations::Calculator;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 10);
+    ///
+    /// let mut genome = neat.empty_genome();
+    /// genome.add_connection(&mut neat, 0, 3);
+    /// genome.add_connection(&mut neat, 1, 3);
+    /// genome.connections.data[1].enabled = false;
+    ///
+    /// let dot = Calculator::new(genome).to_dot();
+    /// assert!(dot.starts_with("digraph network {"));
+    /// assert!(dot.contains("1 -> 4"));
+    /// assert!(!dot.contains("2 -> 4"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let all_nodes: Vec<&Rc<RefCell<Node<F>>>> = self
+            .input_nodes
+            .iter()
+            .chain(&self.hidden_nodes)
+            .chain(&self.output_nodes)
+            .collect();
+        let innovation_number = |node: &Rc<RefCell<Node<F>>>| {
+            all_nodes
+                .iter()
+                .position(|other| Rc::ptr_eq(node, other))
+                .map(|index| self.innovation_numbers[index])
+        };
+
+        // The bias node is always the last input node
+        let bias = self.input_nodes.len() - 1;
+        let nodes: Vec<(u32, NodeType, f32)> = all_nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let node_type = match index {
+                    index if index < bias => NodeType::Input,
+                    index if index == bias => NodeType::Bias,
+                    index if index < bias + 1 + self.hidden_nodes.len() => NodeType::Hidden,
+                    _ => NodeType::Output,
+                };
+                (self.innovation_numbers[index], node_type, node.borrow().x)
+            })
+            .collect();
+
+        let mut connections = Vec::new();
+        for to in self.hidden_nodes.iter().chain(&self.output_nodes) {
+            for connection in &to.borrow().connections {
+                let connection = connection.borrow();
+                if !connection.enabled {
+                    continue;
+                }
+
+                if let (Some(from), Some(to)) =
+                    (innovation_number(&connection.from), innovation_number(to))
+                {
+                    connections.push((from, to, connection.weight.to_f32(), true));
+                }
+            }
+        }
+
+        genome::write_dot("network", &nodes, &connections)
+    }
+
+    /// Enable or disable plasticity, which is [`Config::plasticity`] by default
+    ///
+    /// With plasticity, the weight of every enabled connection changes after every calculation
+    /// with the [`Hebbian`](crate::genome::Hebbian) coefficients of its connection gene.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    /// use profqu_neat::calculations::Calculator;
+    /// use profqu_neat::genome::Hebbian;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(1, 1, 10);
+    ///
+    /// // The weight grows by 0.5 every time the input is on
+    /// let mut genome = neat.empty_genome();
+    /// genome.add_connection(&mut neat, 0, 2);
+    /// genome.connections.data[0].weight = 0.0;
+    /// genome.connections.data[0].hebbian = Hebbian::new(0.0, 1.0, 0.0, 0.0, 0.5);
+    ///
+    /// let mut calc = Calculator::new(genome);
+    /// calc.set_plasticity(true);
+    ///
+    /// assert_eq!(calc.calculate(&vec![1.0]).unwrap(), vec![0.5]);
+    /// assert_eq!(calc.calculate(&vec![1.0]).unwrap(), vec![1.0 / (1.0 + (-0.5_f32).exp())]);
+    ///
+    /// // Forget what was learned, like at the start of a new episode
+    /// calc.reset_weights();
+    /// assert_eq!(calc.calculate(&vec![1.0]).unwrap(), vec![0.5]);
+    /// ```
+    pub fn set_plasticity(&mut self, plasticity: bool) {
+        self.plasticity = plasticity;
+    }
+
+    /// Return if the weights change after every calculation
+    pub fn plasticity(&self) -> bool {
+        self.plasticity
+    }
+
+    /// Set every weight back to the weight of its connection gene
+    pub fn reset_weights(&mut self) {
+        for node in self.hidden_nodes.iter().chain(&self.output_nodes) {
+            for connection in &node.borrow().connections {
+                let mut connection = connection.borrow_mut();
+                connection.weight = connection.initial_weight;
+            }
+        }
+    }
+
+    /// Change the weights with the ABCD rule, using the outputs of the last calculation
+    fn update_weights(&mut self) {
+        for node in self.hidden_nodes.iter().chain(&self.output_nodes) {
+            let node = node.borrow();
+            let post = node.output;
+
+            for connection in &node.connections {
+                let mut connection = connection.borrow_mut();
+                if !connection.enabled {
+                    continue;
+                }
+
+                let pre = connection.from.borrow().output;
+                let hebbian = connection.hebbian;
+                let change = F::from_f32(hebbian.a) * pre * post
+                    + F::from_f32(hebbian.b) * pre
+                    + F::from_f32(hebbian.c) * post
+                    + F::from_f32(hebbian.d);
+
+                connection.weight += F::from_f32(hebbian.learning_rate) * change;
+            }
+        }
+    }
+
+    /// Calculate the outputs like [`Calculator::calculate`], but return the values of every node instead
+    ///
+    /// The values are keyed by the innovation number of the node gene. The sum and the output of input nodes
+    /// and the bias are both the value they were given.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    /// use profqu_neat::calculations::Calculator;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 10);
+    ///
+    /// let mut genome = neat.empty_genome();
+    /// genome.add_connection(&mut neat, 0, 3);
+    ///
+    /// let mut calc = Calculator::new(genome);
+    /// let trace = calc.calculate_traced(&vec![1.0, 0.0]).unwrap();
+    ///
+    /// // The inputs are 1 and 2, the bias is 3 and the output is 4
+    /// assert_eq!(trace[&1].output, 1.0);
+    /// assert_eq!(trace[&3].output, 1.0);
+    /// assert_eq!(trace[&4].sum, 1.0);
+    /// assert_eq!(trace[&4].output, 0.731_058_6);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the number of inputs aren't equal to the number of input nodes.
+    pub fn calculate_traced(
+        &mut self,
+        inputs: &Vec<F>,
+    ) -> Result<HashMap<u32, NodeTrace<F>>, &'static str> {
+        self.calculate(inputs)?;
+
+        let nodes = self
+            .input_nodes
+            .iter()
+            .chain(&self.hidden_nodes)
+            .chain(&self.output_nodes);
+
+        Ok(self
+            .innovation_numbers
+            .iter()
+            .zip(nodes)
+            .map(|(innovation_number, node)| {
+                let node = node.borrow();
+                let trace = NodeTrace {
+                    sum: node.sum,
+                    output: node.output,
+                };
+                (*innovation_number, trace)
+            })
+            .collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Neat;
+    use crate::{genome::Hebbian, Neat};
 
     use super::*;
 
@@ -261,4 +510,230 @@ mod tests {
         );
         assert_eq!(calc.input_nodes.last().unwrap().borrow().output, 1.0);
     }
+
+    #[test]
+    fn calculate_topological_order() {
+        Neat::test_config();
+        let mut neat = Neat::new(1, 1, 10);
+
+        let mut genome = neat.empty_genome();
+        let input = genome.nodes.data[0];
+        let output = genome.nodes.data[2];
+
+        // Both nodes have the same x, so only the connections decide the order
+        let hidden1 = neat.create_node(0.5, 0.3);
+        let hidden2 = neat.create_node(0.5, 0.6);
+        genome.nodes.add(hidden1);
+        genome.nodes.add(hidden2);
+
+        genome.connections.add(neat.get_connection(input, hidden2));
+        genome
+            .connections
+            .add(neat.get_connection(hidden2, hidden1));
+        genome.connections.add(neat.get_connection(hidden1, output));
+
+        let sigmoid = |x: f32| 1.0 / (1.0 + (-x).exp());
+
+        let mut calc = Calculator::new(genome);
+        assert_eq!(
+            calc.calculate(&vec![2.0]).unwrap(),
+            vec![sigmoid(sigmoid(sigmoid(2.0)))]
+        );
+    }
+
+    #[test]
+    fn calculate_without_recurrent() {
+        Neat::test_config();
+        let mut neat = Neat::new(1, 1, 10);
+
+        let mut genome = neat.empty_genome();
+        let input = genome.nodes.data[0];
+        let output = genome.nodes.data[2];
+
+        let hidden1 = neat.create_node(0.3, 0.5);
+        let hidden2 = neat.create_node(0.6, 0.5);
+        genome.nodes.add(hidden1);
+        genome.nodes.add(hidden2);
+
+        genome.connections.add(neat.get_connection(input, hidden1));
+        genome
+            .connections
+            .add(neat.get_connection(hidden1, hidden2));
+        genome.connections.add(neat.get_connection(hidden2, output));
+
+        // Loops and connections back to an earlier node are left out
+        genome
+            .connections
+            .add(neat.get_connection(hidden1, hidden1));
+        genome.connections.add(neat.get_connection(output, hidden1));
+        genome.connections.add(neat.get_connection(output, output));
+
+        let sigmoid = |x: f32| 1.0 / (1.0 + (-x).exp());
+
+        let mut calc = Calculator::new(genome);
+        for _ in 0..2 {
+            assert_eq!(
+                calc.calculate(&vec![2.0]).unwrap(),
+                vec![sigmoid(sigmoid(sigmoid(2.0)))]
+            );
+        }
+    }
+
+    #[test]
+    fn calculate_f64() {
+        Neat::test_config();
+        let mut neat = Neat::new(3, 1, 10);
+
+        let mut genome = neat.empty_genome();
+        genome.add_connection(&mut neat, 0, 4);
+        genome.add_connection(&mut neat, 1, 4);
+        genome.add_connection(&mut neat, 2, 4);
+
+        // The 1.0 gets lost when it's added to 1e8 as an f32
+        let mut calc = Calculator::new(genome.clone());
+        assert_eq!(calc.calculate(&vec![1e8, 1.0, -1e8]).unwrap(), vec![0.5]);
+
+        let mut calc = Calculator::<f64>::from_genome(genome);
+        assert_eq!(
+            calc.calculate(&vec![1e8, 1.0, -1e8]).unwrap(),
+            vec![1.0 / (1.0 + (-1.0_f64).exp())]
+        );
+    }
+    #[test]
+    fn calculate_traced() {
+        Neat::test_config();
+        let mut neat = Neat::new(1, 1, 10);
+
+        let mut genome = neat.empty_genome();
+        let input = genome.nodes.data[0];
+        let bias = genome.nodes.data[1];
+        let output = genome.nodes.data[2];
+
+        let hidden = neat.create_node(0.5, 0.5);
+        genome.nodes.add(hidden);
+
+        let mut first = neat.get_connection(input, hidden);
+        first.weight = 0.5;
+        let mut second = neat.get_connection(hidden, output);
+        second.weight = -2.0;
+        genome.connections.add(first);
+        genome.connections.add(second);
+
+        let sigmoid = |x: f32| 1.0 / (1.0 + (-x).exp());
+
+        let mut calc = Calculator::new(genome);
+        assert_eq!(
+            calc.calculate_traced(&vec![1.0, 2.0]),
+            Err("Number of inputs aren't equal to number of input nodes")
+        );
+
+        let trace = calc.calculate_traced(&vec![3.0]).unwrap();
+        assert_eq!(trace.len(), 4);
+        assert_eq!(
+            trace[&input.innovation_number],
+            NodeTrace {
+                sum: 3.0,
+                output: 3.0
+            }
+        );
+        assert_eq!(
+            trace[&bias.innovation_number],
+            NodeTrace {
+                sum: 1.0,
+                output: 1.0
+            }
+        );
+        assert_eq!(
+            trace[&hidden.innovation_number],
+            NodeTrace {
+                sum: 1.5,
+                output: sigmoid(1.5)
+            }
+        );
+        assert_eq!(
+            trace[&output.innovation_number],
+            NodeTrace {
+                sum: -2.0 * sigmoid(1.5),
+                output: sigmoid(-2.0 * sigmoid(1.5))
+            }
+        );
+    }
+
+    #[test]
+    fn plasticity() {
+        Neat::test_config();
+        let mut neat = Neat::new(2, 1, 10);
+
+        let mut genome = neat.empty_genome();
+        genome.add_connection(&mut neat, 0, 3);
+        genome.add_connection(&mut neat, 1, 3);
+        genome.connections.data[0].hebbian = Hebbian::new(1.0, 2.0, 3.0, 4.0, 0.5);
+        genome.connections.data[1].hebbian = Hebbian::new(1.0, 1.0, 1.0, 1.0, 1.0);
+        genome.connections.data[1].enabled = false;
+
+        let sigmoid = |x: f32| 1.0 / (1.0 + (-x).exp());
+
+        let mut calc = Calculator::new(genome.clone());
+        assert!(!calc.plasticity());
+        calc.calculate(&vec![2.0, 1.0]).unwrap();
+        assert_eq!(calc.calculate(&vec![2.0, 1.0]).unwrap(), vec![sigmoid(2.0)]);
+
+        calc.set_plasticity(true);
+        let post = calc.calculate(&vec![2.0, 1.0]).unwrap()[0];
+
+        // Only the enabled connection learns
+        let weight = 1.0 + 0.5 * (2.0 * post + 2.0 * 2.0 + 3.0 * post + 4.0);
+        let weights: Vec<f32> = calc.output_nodes[0]
+            .borrow()
+            .connections
+            .iter()
+            .map(|connection| connection.borrow().weight)
+            .collect();
+        assert_eq!(weights, vec![weight, 1.0]);
+
+        assert_eq!(
+            calc.calculate(&vec![2.0, 1.0]).unwrap(),
+            vec![sigmoid(2.0 * weight)]
+        );
+
+        calc.reset_weights();
+        calc.set_plasticity(false);
+        assert_eq!(calc.calculate(&vec![2.0, 1.0]).unwrap(), vec![sigmoid(2.0)]);
+
+        // The same rule with f64
+        let mut calc = Calculator::<f64>::from_genome(genome);
+        calc.set_plasticity(true);
+        calc.calculate(&vec![0.0, 0.0]).unwrap();
+        assert_eq!(
+            calc.output_nodes[0].borrow().connections[0].borrow().weight,
+            1.0 + 0.5 * (3.0 * 0.5 + 4.0)
+        );
+    }
+
+    #[test]
+    fn to_dot() {
+        Neat::test_config();
+        let mut neat = Neat::new(1, 1, 10);
+
+        let mut genome = neat.empty_genome();
+        genome.add_connection(&mut neat, 0, 2);
+        genome.mutate_node(&mut neat);
+        genome.connections.data[0].hebbian = Hebbian::new(0.0, 0.0, 0.0, 1.0, 1.0);
+
+        let mut calc = Calculator::new(genome);
+        let dot = calc.to_dot();
+        assert!(dot.contains("{ rank=min; 1 [shape=box, fillcolor=lightblue]; 2 [shape=diamond, fillcolor=lightgrey]; }"));
+        assert!(dot.contains("{ rank=same; 4 [shape=circle]; }"));
+        assert!(dot.contains("{ rank=max; 3 [shape=doublecircle, fillcolor=lightgreen]; }"));
+        assert!(
+            dot.contains("1 -> 4 [color=forestgreen, penwidth=1.50, style=solid, label=\"1.00\"];")
+        );
+
+        // The learned weight is drawn instead of the genome's
+        calc.set_plasticity(true);
+        calc.calculate(&vec![1.0]).unwrap();
+        assert!(calc
+            .to_dot()
+            .contains("1 -> 4 [color=forestgreen, penwidth=2.50, style=solid, label=\"2.00\"];"));
+    }
 }
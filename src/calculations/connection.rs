@@ -1,26 +1,33 @@
-use super::Node;
+use super::{Float, Node};
+use crate::genome::Hebbian;
 
 use std::cell::RefCell;
 use std::rc::Rc;
 
 /// The connection for calculations
 #[derive(PartialEq, Clone, Debug)]
-pub struct Connection {
+pub struct Connection<F: Float = f32> {
     /// Pointers to the nodes this connection is from
-    pub from: Rc<RefCell<Node>>,
+    pub from: Rc<RefCell<Node<F>>>,
     /// The weight of this connection
-    pub weight: f32,
+    pub weight: F,
     /// Whether this connection is enabled or not
     pub enabled: bool,
+    /// The weight of the connection gene, which the weight is reset to
+    pub initial_weight: F,
+    /// The coefficients that change the weight after every calculation when plasticity is enabled
+    pub hebbian: Hebbian,
 }
 
-impl Connection {
+impl<F: Float> Connection<F> {
     /// Create a new connection using a reference to a cell where it comes from
-    pub fn new(from: Rc<RefCell<Node>>) -> Self {
+    pub fn new(from: Rc<RefCell<Node<F>>>) -> Self {
         Self {
             from,
-            weight: 1.0,
+            weight: F::ONE,
             enabled: true,
+            initial_weight: F::ONE,
+            hebbian: Hebbian::default(),
         }
     }
 }
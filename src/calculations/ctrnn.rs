@@ -0,0 +1,192 @@
+use super::recurrent::Recurrent;
+use crate::genome::Genome;
+use crate::neat::{ActivationFunction, Config};
+
+/// A continuous-time recurrent neural network, used instead of a [`Calculator`](super::Calculator)
+/// for networks that change over time
+///
+/// Every node that isn't an input or the bias has a state `y`, which follows
+/// `time_constant * dy/dt = -y + sum(weight * output)`, where the output of a node is `activation(y + bias)`.
+/// The states are integrated with Euler steps, so `dt` should be smaller than the time constants.
+/// Unlike the `Calculator`, connections may form cycles, which evolution creates when
+/// [`Config::allow_recurrent`] is set.
+///
+/// # Examples
+///
+/// ```rust
+/// use profqu_neat::Neat;
+/// use profqu_neat::calculations::Ctrnn;
+///
+/// Neat::test_config();
+/// let mut neat = Neat::new(1, 1, 10);
+///
+/// let mut genome = neat.empty_genome();
+/// genome.add_connection(&mut neat, 0, 2);
+/// genome.connections.data[0].weight = 2.0;
+///
+/// let mut ctrnn = Ctrnn::new(&genome);
+///
+/// // The state of the output slowly moves towards its input of 2.0
+/// let outputs = ctrnn.advance(&vec![1.0], 0.1, 100).unwrap();
+/// assert!((outputs[0] - 0.880_797).abs() < 1e-4);
+///
+/// ctrnn.reset();
+/// assert_eq!(ctrnn.advance(&vec![1.0], 0.1, 0).unwrap(), vec![0.5]);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Ctrnn {
+    activation: ActivationFunction,
+    input_size: usize,
+    time_constants: Vec<f32>,
+    biases: Vec<f32>,
+    states: Vec<f32>,
+    /// The index of the value every connection comes from, the index of the node it goes to and its weight
+    connections: Vec<(usize, usize, f32)>,
+    /// The indices of the output nodes
+    outputs: Vec<usize>,
+}
+
+impl Ctrnn {
+    /// Create a new CTRNN from a genome, with all states at zero
+    pub fn new(genome: &Genome) -> Self {
+        let recurrent = Recurrent::new(genome);
+
+        Self {
+            activation: Config::global().activation,
+            input_size: recurrent.input_size,
+            time_constants: recurrent
+                .nodes
+                .iter()
+                .map(|node| node.time_constant)
+                .collect(),
+            biases: recurrent.nodes.iter().map(|node| node.bias).collect(),
+            states: vec![0.0; recurrent.nodes.len()],
+            outputs: (recurrent.output_start..recurrent.nodes.len()).collect(),
+            connections: recurrent.connections,
+        }
+    }
+
+    /// Integrate the states for a number of steps of `dt` with the same inputs and return the outputs
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the number of inputs aren't equal to the number of input nodes.
+    pub fn advance(
+        &mut self,
+        inputs: &Vec<f32>,
+        dt: f32,
+        steps: usize,
+    ) -> Result<Vec<f32>, &'static str> {
+        if inputs.len() != self.input_size {
+            return Err("Number of inputs aren't equal to number of input nodes");
+        }
+
+        let mut values = inputs.clone();
+        values.push(1.0);
+        values.resize(self.input_size + 1 + self.states.len(), 0.0);
+
+        let mut sums = vec![0.0; self.states.len()];
+
+        for _ in 0..steps {
+            for (node, state) in self.states.iter().enumerate() {
+                values[self.input_size + 1 + node] = self.output(node, *state);
+            }
+
+            sums.fill(0.0);
+            for (from, to, weight) in &self.connections {
+                sums[*to] += weight * values[*from];
+            }
+
+            for (node, state) in self.states.iter_mut().enumerate() {
+                *state += dt / self.time_constants[node] * (sums[node] - *state);
+            }
+        }
+
+        Ok(self
+            .outputs
+            .iter()
+            .map(|node| self.output(*node, self.states[*node]))
+            .collect())
+    }
+
+    /// Set all states back to zero
+    pub fn reset(&mut self) {
+        self.states.fill(0.0);
+    }
+
+    /// Get the states of the hidden nodes in the order they are calculated in, followed by the output nodes
+    pub fn states(&self) -> &[f32] {
+        &self.states
+    }
+
+    fn output(&self, node: usize, state: f32) -> f32 {
+        self.activation.apply(state + self.biases[node])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Neat;
+
+    fn sigmoid(x: f32) -> f32 {
+        1.0 / (1.0 + (-x).exp())
+    }
+
+    #[test]
+    fn advance() {
+        Neat::test_config();
+        let mut neat = Neat::new(1, 1, 10);
+
+        // The output is connected to the input and to itself
+        let mut genome = neat.empty_genome();
+        let (input, mut output) = (genome.nodes.data[0], genome.nodes.data[2]);
+        let mut recurrent = neat.get_connection(output, output);
+        recurrent.weight = 0.5;
+        genome.connections.add(neat.get_connection(input, output));
+        genome.connections.add(recurrent);
+
+        output.time_constant = 0.5;
+        output.bias = 0.25;
+        genome.update_node(output);
+
+        let mut ctrnn = Ctrnn::new(&genome);
+        assert_eq!(
+            ctrnn.advance(&vec![1.0, 2.0], 0.1, 1),
+            Err("Number of inputs aren't equal to number of input nodes")
+        );
+
+        let first = 0.2 * (1.0 + 0.5 * sigmoid(0.25));
+        let second = first + 0.2 * (1.0 + 0.5 * sigmoid(first + 0.25) - first);
+
+        let outputs = ctrnn.advance(&vec![1.0], 0.1, 1).unwrap();
+        assert!((outputs[0] - sigmoid(first + 0.25)).abs() < 1e-6);
+
+        // The state is kept between calls
+        let outputs = ctrnn.advance(&vec![1.0], 0.1, 1).unwrap();
+        assert!((outputs[0] - sigmoid(second + 0.25)).abs() < 1e-6);
+        assert!((ctrnn.states()[0] - second).abs() < 1e-6);
+
+        ctrnn.reset();
+        assert_eq!(ctrnn.states(), &[0.0]);
+        assert_eq!(
+            ctrnn.advance(&vec![1.0], 0.1, 0).unwrap(),
+            vec![sigmoid(0.25)]
+        );
+    }
+
+    #[test]
+    fn disabled_connections() {
+        Neat::test_config();
+        let mut neat = Neat::new(2, 1, 10);
+
+        let mut genome = neat.empty_genome();
+        genome.add_connection(&mut neat, 0, 3);
+        genome.add_connection(&mut neat, 1, 3);
+        genome.connections.data[1].enabled = false;
+
+        let mut ctrnn = Ctrnn::new(&genome);
+        ctrnn.advance(&vec![1.0, 5.0], 0.5, 1).unwrap();
+        assert_eq!(ctrnn.states(), &[0.5]);
+    }
+}
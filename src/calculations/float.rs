@@ -0,0 +1,66 @@
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, Mul, Neg};
+
+/// A floating point type the [`Calculator`](super::Calculator) can calculate with
+///
+/// It's implemented for `f32` and `f64`, the weights of the genome are converted with [`Float::from_f32`].
+pub trait Float:
+    Copy
+    + Debug
+    + PartialEq
+    + PartialOrd
+    + Add<Output = Self>
+    + AddAssign
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Zero
+    const ZERO: Self;
+    /// One
+    const ONE: Self;
+
+    /// Convert an `f32` to this type
+    fn from_f32(value: f32) -> Self;
+
+    /// Convert this to an `f32`, possibly losing precision
+    fn to_f32(self) -> f32;
+
+    /// Calculate `e^self`
+    #[must_use]
+    fn exp(self) -> Self;
+}
+
+impl Float for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    fn from_f32(value: f32) -> Self {
+        value
+    }
+
+    fn to_f32(self) -> f32 {
+        self
+    }
+
+    fn exp(self) -> Self {
+        f32::exp(self)
+    }
+}
+
+impl Float for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    fn from_f32(value: f32) -> Self {
+        f64::from(value)
+    }
+
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+}
@@ -2,11 +2,23 @@
 //!
 //! It contains a `Calculator` which is created by a client to calculate it's genome's output with some input.
 //! The `Connection` and `Node` structs are used in the `Calculator` to calculate the outputs.
+//! A `Ctrnn` calculates genomes as continuous-time recurrent networks, which keep their state between calls.
+//! A `SpikingNetwork` steps genomes in time as networks of Izhikevich spiking neurons.
+//! All of them calculate with `f32` by default, but can use any type that implements `Float`, like `f64`.
 
 mod calculator;
 mod connection;
+mod ctrnn;
+mod float;
 mod node;
+mod node_trace;
+mod recurrent;
+mod spiking_network;
 
 pub use calculator::Calculator;
 pub use connection::Connection;
+pub use ctrnn::Ctrnn;
+pub use float::Float;
 pub use node::Node;
+pub use node_trace::NodeTrace;
+pub use spiking_network::SpikingNetwork;
@@ -2,26 +2,29 @@ use std::{cell::RefCell, rc::Rc};
 
 use crate::neat::{ActivationFunction, Config};
 
-use super::Connection;
+use super::{Connection, Float};
 
 /// The node for calculations
 #[derive(PartialEq, Clone, Debug)]
-pub struct Node {
-    /// The x coordinate of the node, used for checking if a connection would be recursive
+pub struct Node<F: Float = f32> {
+    /// The x coordinate of the node
     pub x: f32,
+    /// The sum of the incoming connections, before the activation function
+    pub sum: F,
     /// The calculated output, calculated according to the incoming connections
-    pub output: f32,
+    pub output: F,
     /// The incoming connections to this node
-    pub connections: Vec<Rc<RefCell<Connection>>>,
-    activation: fn(f32) -> f32,
+    pub connections: Vec<Rc<RefCell<Connection<F>>>>,
+    activation: fn(F) -> F,
 }
 
-impl Node {
+impl<F: Float> Node<F> {
     /// Create a new node, with a certain x value
     pub fn new(x: f32) -> Self {
         Self {
             x,
-            output: 0.,
+            sum: F::ZERO,
+            output: F::ZERO,
             connections: Vec::new(),
             activation: match Config::global().activation {
                 ActivationFunction::Relu => Self::relu_activation,
@@ -32,7 +35,7 @@ impl Node {
 
     /// Calculate a the output value given all it's incoming connections
     pub fn calculate(&mut self) {
-        let mut sum = 0.0;
+        let mut sum = F::ZERO;
 
         for connection in &self.connections {
             if connection.borrow().enabled {
@@ -40,31 +43,32 @@ impl Node {
             }
         }
 
+        self.sum = sum;
         self.output = (self.activation)(sum);
     }
 
-    fn relu_activation(input: f32) -> f32 {
-        if input <= 0.0 {
-            0.0
+    fn relu_activation(input: F) -> F {
+        if input <= F::ZERO {
+            F::ZERO
         } else {
             input
         }
     }
 
-    fn sigmoid_activation(input: f32) -> f32 {
-        1.0 / (1.0 + (-input).exp())
+    fn sigmoid_activation(input: F) -> F {
+        F::ONE / (F::ONE + (-input).exp())
     }
 }
 
-impl PartialOrd for Node {
+impl<F: Float> PartialOrd for Node<F> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         self.x.partial_cmp(&other.x)
     }
 }
 
-impl Eq for Node {}
+impl<F: Float> Eq for Node<F> {}
 
-impl Ord for Node {
+impl<F: Float> Ord for Node<F> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.x.total_cmp(&other.x)
     }
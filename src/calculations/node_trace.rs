@@ -0,0 +1,10 @@
+use super::Float;
+
+/// The values of a node during the last calculation, given by [`Calculator::calculate_traced`](super::Calculator::calculate_traced)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NodeTrace<F: Float = f32> {
+    /// The sum of the incoming connections, before the activation function
+    pub sum: F,
+    /// The output of the node, after the activation function
+    pub output: F,
+}
@@ -0,0 +1,51 @@
+use crate::genome::{Genome, NodeGene};
+
+/// The nodes and enabled connections of a genome, for networks that are stepped in time and may have cycles
+///
+/// The values of the network are the inputs, the bias and then the hidden and output nodes.
+pub(super) struct Recurrent {
+    pub(super) input_size: usize,
+    /// The hidden nodes in the order they are calculated in, followed by the output nodes
+    pub(super) nodes: Vec<NodeGene>,
+    /// The index of the first output node in `nodes`
+    pub(super) output_start: usize,
+    /// The index of the value every connection comes from, the index of the node it goes to and its weight
+    pub(super) connections: Vec<(usize, usize, f32)>,
+}
+
+impl Recurrent {
+    pub(super) fn new(genome: &Genome) -> Self {
+        let (input_nodes, bias, hidden_nodes, output_nodes) = genome.evaluation_order();
+        let output_start = hidden_nodes.len();
+        let nodes: Vec<NodeGene> = hidden_nodes.into_iter().chain(output_nodes).collect();
+
+        let value_index = |innovation_number: u32| {
+            input_nodes
+                .iter()
+                .chain(&bias)
+                .chain(&nodes)
+                .position(|node| node.innovation_number == innovation_number)
+        };
+
+        let connections = genome
+            .connections
+            .data
+            .iter()
+            .filter(|connection| connection.enabled)
+            .filter_map(|connection| {
+                let from = value_index(connection.from.innovation_number)?;
+                let to = value_index(connection.to.innovation_number)?;
+
+                (to > input_nodes.len())
+                    .then(|| (from, to - input_nodes.len() - 1, connection.weight))
+            })
+            .collect();
+
+        Self {
+            input_size: input_nodes.len(),
+            nodes,
+            output_start,
+            connections,
+        }
+    }
+}
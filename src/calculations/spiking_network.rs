@@ -0,0 +1,266 @@
+use super::recurrent::Recurrent;
+use crate::genome::{Genome, Izhikevich};
+
+/// The membrane potential in mV at which a neuron spikes
+const SPIKE_THRESHOLD: f32 = 30.0;
+
+/// A network of Izhikevich spiking neurons, used instead of a [`Calculator`](super::Calculator)
+/// for neuromorphic controllers
+///
+/// Every node that isn't an input or the bias is a neuron with the [`Izhikevich`] parameters of its node gene.
+/// The current into a neuron is the weighted sum of the inputs, the bias and the neurons that spiked in the
+/// previous step, multiplied by the [`current_scale`](SpikingNetwork::current_scale).
+/// Time is measured in ms and the neurons are integrated with Euler steps, so `dt` should be at most 1.0.
+/// Connections may form cycles, which evolution creates when
+/// [`Config::allow_recurrent`](crate::neat::Config::allow_recurrent) is set.
+///
+/// # Examples
+///
+/// ```rust
+/// use profqu_neat::Neat;
+/// use profqu_neat::calculations::SpikingNetwork;
+///
+/// Neat::test_config();
+/// let mut neat = Neat::new(1, 1, 10);
+///
+/// let mut genome = neat.empty_genome();
+/// genome.add_connection(&mut neat, 0, 2);
+///
+/// let mut network = SpikingNetwork::new(&genome);
+///
+/// // Step for 200 ms
+/// for _ in 0..400 {
+///     network.step(&vec![1.0], 0.5).unwrap();
+/// }
+///
+/// let spikes = &network.spike_trains()[0];
+/// assert!(!spikes.is_empty());
+/// println!("The output spiked at {spikes:?} ms");
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct SpikingNetwork {
+    /// The current that a connection with a weight of 1.0 gives for an input of 1.0 or a spike
+    pub current_scale: f32,
+    input_size: usize,
+    parameters: Vec<Izhikevich>,
+    potentials: Vec<f32>,
+    recovery: Vec<f32>,
+    spiked: Vec<bool>,
+    /// The index of the value every connection comes from, the index of the neuron it goes to and its weight
+    connections: Vec<(usize, usize, f32)>,
+    /// The index of the first output neuron
+    output_start: usize,
+    time: f32,
+    spike_trains: Vec<Vec<f32>>,
+}
+
+impl SpikingNetwork {
+    /// Create a new spiking network from a genome with a current scale of 10.0, with all neurons at rest
+    pub fn new(genome: &Genome) -> Self {
+        let recurrent = Recurrent::new(genome);
+        let parameters: Vec<Izhikevich> =
+            recurrent.nodes.iter().map(|node| node.izhikevich).collect();
+
+        let mut network = Self {
+            current_scale: 10.0,
+            input_size: recurrent.input_size,
+            potentials: vec![0.0; parameters.len()],
+            recovery: vec![0.0; parameters.len()],
+            spiked: vec![false; parameters.len()],
+            spike_trains: vec![Vec::new(); parameters.len() - recurrent.output_start],
+            parameters,
+            connections: recurrent.connections,
+            output_start: recurrent.output_start,
+            time: 0.0,
+        };
+
+        network.reset();
+        network
+    }
+
+    /// Advance the network by `dt` ms with some inputs and return which output neurons spiked
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the number of inputs aren't equal to the number of input nodes.
+    pub fn step(&mut self, inputs: &Vec<f32>, dt: f32) -> Result<Vec<bool>, &'static str> {
+        if inputs.len() != self.input_size {
+            return Err("Number of inputs aren't equal to number of input nodes");
+        }
+
+        let mut values = inputs.clone();
+        values.push(1.0);
+        values.extend(
+            self.spiked
+                .iter()
+                .map(|spiked| f32::from(u8::from(*spiked))),
+        );
+
+        let mut currents = vec![0.0; self.parameters.len()];
+        for (from, to, weight) in &self.connections {
+            currents[*to] += weight * values[*from];
+        }
+
+        self.time += dt;
+
+        for (neuron, current) in currents.into_iter().enumerate() {
+            let Izhikevich { a, b, c, d } = self.parameters[neuron];
+            let (v, u) = (self.potentials[neuron], self.recovery[neuron]);
+            let current = self.current_scale * current;
+
+            self.potentials[neuron] = v + dt * (0.04 * v * v + 5.0 * v + 140.0 - u + current);
+            self.recovery[neuron] = u + dt * a * (b * v - u);
+            self.spiked[neuron] = self.potentials[neuron] >= SPIKE_THRESHOLD;
+
+            if self.spiked[neuron] {
+                self.potentials[neuron] = c;
+                self.recovery[neuron] += d;
+
+                if let Some(output) = neuron.checked_sub(self.output_start) {
+                    self.spike_trains[output].push(self.time);
+                }
+            }
+        }
+
+        Ok(self.spiked[self.output_start..].to_vec())
+    }
+
+    /// Get the times in ms at which every output neuron spiked since the last reset
+    pub fn spike_trains(&self) -> &[Vec<f32>] {
+        &self.spike_trains
+    }
+
+    /// Get the number of spikes per second of every output neuron since the last reset
+    pub fn firing_rates(&self) -> Vec<f32> {
+        self.spike_trains
+            .iter()
+            .map(|spikes| {
+                if self.time > 0.0 {
+                    1000.0 * spikes.len() as f32 / self.time
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+
+    /// Get the membrane potentials in mV of the hidden neurons in the order they are calculated in,
+    /// followed by the output neurons
+    pub fn potentials(&self) -> &[f32] {
+        &self.potentials
+    }
+
+    /// Get the time in ms since the last reset
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Put every neuron back at rest and clear the spike trains
+    pub fn reset(&mut self) {
+        for (neuron, parameters) in self.parameters.iter().enumerate() {
+            self.potentials[neuron] = parameters.c;
+            self.recovery[neuron] = parameters.b * parameters.c;
+        }
+
+        self.spiked.fill(false);
+        self.spike_trains.iter_mut().for_each(Vec::clear);
+        self.time = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{genome::NodeType, Neat};
+
+    #[test]
+    fn step() {
+        Neat::test_config();
+        let mut neat = Neat::new(1, 1, 10);
+
+        let mut genome = neat.empty_genome();
+        genome.add_connection(&mut neat, 0, 2);
+
+        let mut network = SpikingNetwork::new(&genome);
+        assert_eq!(
+            network.step(&vec![1.0, 1.0], 0.5),
+            Err("Number of inputs aren't equal to number of input nodes")
+        );
+
+        // Without an input the neuron stays at rest
+        for _ in 0..400 {
+            assert_eq!(network.step(&vec![0.0], 0.5).unwrap(), vec![false]);
+        }
+        assert!(network.potentials()[0] < -60.0);
+
+        network.reset();
+        let steps = (0..400)
+            .filter(|_| network.step(&vec![1.0], 0.5).unwrap()[0])
+            .count();
+
+        let spikes = &network.spike_trains()[0];
+        assert!(steps > 1);
+        assert_eq!(spikes.len(), steps);
+        assert!(spikes.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(network.time(), 200.0);
+        assert_eq!(network.firing_rates(), vec![5.0 * steps as f32]);
+
+        network.reset();
+        assert!(network.spike_trains()[0].is_empty());
+        assert_eq!(network.potentials(), &[-65.0]);
+    }
+
+    #[test]
+    fn parameters() {
+        Neat::test_config();
+        let mut neat = Neat::new(1, 2, 10);
+
+        let mut genome = neat.empty_genome();
+        genome.add_connection(&mut neat, 0, 2);
+        genome.add_connection(&mut neat, 0, 3);
+
+        let mut fast = genome.nodes.data[3];
+        fast.izhikevich = Izhikevich::FAST_SPIKING;
+        genome.update_node(fast);
+
+        // A fast spiking neuron doesn't adapt, so it spikes more than a regular spiking one
+        let mut network = SpikingNetwork::new(&genome);
+        for _ in 0..1000 {
+            network.step(&vec![1.0], 0.5).unwrap();
+        }
+
+        let rates = network.firing_rates();
+        assert!(rates[1] > rates[0]);
+    }
+
+    #[test]
+    fn hidden_spikes() {
+        Neat::test_config();
+        let mut neat = Neat::new(1, 1, 10);
+
+        // The output only gets a current from the spikes of the hidden neuron
+        let mut genome = neat.empty_genome();
+        genome.add_connection(&mut neat, 0, 2);
+        genome.mutate_node(&mut neat);
+        for connection in &mut genome.connections.data {
+            if connection.from.node_type == NodeType::Hidden {
+                connection.weight = 20.0;
+            }
+        }
+
+        let mut network = SpikingNetwork::new(&genome);
+        for _ in 0..1000 {
+            network.step(&vec![1.0], 0.5).unwrap();
+        }
+
+        assert!(!network.spike_trains()[0].is_empty());
+
+        // Without the hidden neuron spiking, the output stays quiet
+        network.reset();
+        for _ in 0..1000 {
+            network.step(&vec![0.0], 0.5).unwrap();
+        }
+
+        assert!(network.spike_trains()[0].is_empty());
+    }
+}
@@ -2,7 +2,7 @@ use std::{fmt::Debug, hash::Hash};
 
 use crate::neat;
 
-use super::node_gene::NodeGene;
+use super::{node_gene::NodeGene, Hebbian};
 
 /// The connection gene of some genome
 #[derive(Clone, Copy)]
@@ -17,6 +17,8 @@ pub struct ConnectionGene {
     pub weight: f32,
     /// Whether this gene is enabled
     pub enabled: bool,
+    /// The coefficients that change the weight within a lifetime when plasticity is enabled
+    pub hebbian: Hebbian,
     /// The index for getting this connection instead of creating a new connection
     pub replace_index: usize,
 }
@@ -30,6 +32,7 @@ impl ConnectionGene {
             to,
             weight: 1.0,
             enabled: true,
+            hebbian: Hebbian::default(),
             replace_index: 0,
         }
     }
@@ -39,6 +42,18 @@ impl ConnectionGene {
         u64::from(self.from.innovation_number) * neat::MAX_NODES
             + u64::from(self.to.innovation_number)
     }
+
+    /// Check if this is the same gene as 'other' with the same weight, state and coefficients
+    ///
+    /// Unlike `==`, which only checks if both connect the same nodes.
+    pub fn same_values(&self, other: &Self) -> bool {
+        self == other
+            && self.innovation_number == other.innovation_number
+            && self.enabled == other.enabled
+            && self.weight == other.weight
+            && self.replace_index == other.replace_index
+            && self.hebbian == other.hebbian
+    }
 }
 
 impl Debug for ConnectionGene {
@@ -48,14 +63,12 @@ impl Debug for ConnectionGene {
     }
 }
 
+/// Connection genes are the same gene when they connect the same nodes, like their hash,
+/// so a gene that is changed in place in [`RandomHashSet::data`](crate::data_structures::RandomHashSet::data)
+/// can still be found and removed
 impl PartialEq for ConnectionGene {
     fn eq(&self, other: &Self) -> bool {
-        self.innovation_number == other.innovation_number
-            && self.enabled == other.enabled
-            && self.from == other.from
-            && self.to == other.to
-            && self.weight == other.weight
-            && self.replace_index == other.replace_index
+        self.from == other.from && self.to == other.to
     }
 }
 
@@ -75,6 +88,7 @@ mod tests {
     use rand::Rng;
 
     use super::*;
+    use crate::data_structures::RandomHashSet;
 
     #[test]
     fn test_hash() {
@@ -102,4 +116,38 @@ mod tests {
             assert_eq!(result_connection, result_nodes);
         }
     }
+
+    #[test]
+    fn same_values() {
+        let connection = ConnectionGene::new(NodeGene::new(1), NodeGene::new(2));
+        let mut other = connection;
+        assert!(connection.same_values(&other));
+
+        other.hebbian.learning_rate = 0.1;
+        assert_eq!(connection, other);
+        assert!(!connection.same_values(&other));
+
+        other = connection;
+        other.weight = 0.5;
+        assert_eq!(connection, other);
+        assert!(!connection.same_values(&other));
+    }
+
+    #[test]
+    fn remove_after_change() {
+        let mut set = RandomHashSet::new();
+        set.add(ConnectionGene::new(NodeGene::new(1), NodeGene::new(2)));
+        set.add(ConnectionGene::new(NodeGene::new(1), NodeGene::new(3)));
+
+        // The copy in the hash set still has the old values
+        set.data[0].weight = 0.5;
+        set.data[0].enabled = false;
+        set.data[0].hebbian.learning_rate = 0.1;
+
+        let changed = set.data[0];
+        assert!(set.contains(&changed));
+        assert!(set.remove_value(&changed));
+        assert_eq!(set.len(), 1);
+        assert!(!set.contains(&changed));
+    }
 }
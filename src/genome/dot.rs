@@ -0,0 +1,163 @@
+use std::fmt::Write;
+
+use super::{Genome, NodeType};
+
+impl Genome {
+    /// Create a [Graphviz](https://graphviz.org) graph of this genome in the DOT language
+    ///
+    /// Nodes with the same `x` coordinate are ranked together, so the inputs are on the left
+    /// and the outputs on the right. Connections are styled like in [`render_svg`](Genome::render_svg).
+    /// Use [`Calculator::to_dot`](crate::calculations::Calculator::to_dot) to draw the network that is actually calculated.
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 10);
+    ///
+    /// let mut genome = neat.empty_genome();
+    /// genome.add_connection(&mut neat, 0, 3);
+    ///
+    /// let dot = genome.to_dot();
+    /// assert!(dot.starts_with("digraph genome {"));
+    /// assert!(dot.contains("1 -> 4"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let nodes: Vec<(u32, NodeType, f32)> = self
+            .nodes
+            .data
+            .iter()
+            .map(|node| (node.innovation_number, node.node_type, node.x))
+            .collect();
+        let connections: Vec<(u32, u32, f32, bool)> = self
+            .connections
+            .data
+            .iter()
+            .map(|connection| {
+                (
+                    connection.from.innovation_number,
+                    connection.to.innovation_number,
+                    connection.weight,
+                    connection.enabled,
+                )
+            })
+            .collect();
+
+        write_dot("genome", &nodes, &connections)
+    }
+}
+
+/// Write a DOT graph of nodes with their innovation number, type and `x` coordinate,
+/// and connections with the innovation numbers they are from and to, their weight and if they are enabled
+///
+/// Nodes with the same `x` are ranked together. The first rank is kept on the left and the last on the right,
+/// and invisible edges keep the ranks in between in order.
+pub(crate) fn write_dot(
+    name: &str,
+    nodes: &[(u32, NodeType, f32)],
+    connections: &[(u32, u32, f32, bool)],
+) -> String {
+    let mut dot = format!("digraph {name} {{\n");
+    dot.push_str("    rankdir=LR;\n");
+    dot.push_str("    node [shape=circle, style=filled, fillcolor=white];\n");
+
+    let mut ranks: Vec<Vec<(u32, NodeType, f32)>> = Vec::new();
+    for node in nodes {
+        match ranks.iter_mut().find(|rank| rank[0].2 == node.2) {
+            Some(rank) => rank.push(*node),
+            None => ranks.push(vec![*node]),
+        }
+    }
+    ranks.sort_by(|a, b| a[0].2.total_cmp(&b[0].2));
+
+    let last = ranks.len().saturating_sub(1);
+    for (index, rank) in ranks.iter().enumerate() {
+        let rank_type = match index {
+            0 if last > 0 => "min",
+            index if index == last && last > 0 => "max",
+            _ => "same",
+        };
+
+        write!(dot, "\n    {{ rank={rank_type};").expect("Failed to write to string");
+        for (innovation_number, node_type, _) in rank {
+            let attributes = match node_type {
+                NodeType::Input => "shape=box, fillcolor=lightblue",
+                NodeType::Bias => "shape=diamond, fillcolor=lightgrey",
+                NodeType::Hidden => "shape=circle",
+                NodeType::Output => "shape=doublecircle, fillcolor=lightgreen",
+            };
+
+            write!(dot, " {innovation_number} [{attributes}];").expect("Failed to write to string");
+        }
+        dot.push_str(" }\n");
+    }
+
+    dot.push('\n');
+    for pair in ranks.windows(2) {
+        writeln!(
+            dot,
+            "    {} -> {} [style=invis];",
+            pair[0][0].0, pair[1][0].0
+        )
+        .expect("Failed to write to string");
+    }
+
+    for (from, to, weight, enabled) in connections {
+        let color = if *weight < 0.0 { "red" } else { "forestgreen" };
+        let penwidth = (0.5 + weight.abs()).min(5.0);
+        let style = if *enabled { "solid" } else { "dashed" };
+
+        writeln!(
+            dot,
+            "    {from} -> {to} [color={color}, penwidth={penwidth:.2}, style={style}, label=\"{weight:.2}\"];"
+        )
+        .expect("Failed to write to string");
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Neat;
+
+    #[test]
+    fn to_dot() {
+        Neat::test_config();
+        let mut neat = Neat::new(2, 1, 10);
+
+        let mut genome = neat.empty_genome();
+        genome.add_connection(&mut neat, 0, 3);
+        genome.add_connection(&mut neat, 1, 3);
+        genome.mutate_node(&mut neat);
+
+        genome.connections.data[0].weight = -2.0;
+        genome.connections.data[0].enabled = false;
+
+        let dot = genome.to_dot();
+
+        assert!(dot.starts_with("digraph genome {\n"));
+        assert!(dot.ends_with("}\n"));
+
+        // Inputs and the bias node are ranked together on the left, then the hidden and the output nodes
+        assert!(dot.contains(
+            "{ rank=min; 1 [shape=box, fillcolor=lightblue]; 2 [shape=box, fillcolor=lightblue]; 3 [shape=diamond, fillcolor=lightgrey]; }"
+        ));
+        assert!(dot.contains("{ rank=same; 5 [shape=circle]; }"));
+        assert!(dot.contains("{ rank=max; 4 [shape=doublecircle, fillcolor=lightgreen]; }"));
+        assert!(dot.contains("1 -> 5 [style=invis];\n    5 -> 4 [style=invis];"));
+
+        let connection = genome.connections.data[0];
+        assert!(dot.contains(&format!(
+            "{} -> {} [color=red, penwidth=2.50, style=dashed, label=\"-2.00\"];",
+            connection.from.innovation_number, connection.to.innovation_number
+        )));
+
+        assert_eq!(dot.matches("label=").count(), genome.connections.len());
+        assert_eq!(
+            dot.matches("style=solid").count(),
+            genome.connections.len() - 1
+        );
+    }
+}
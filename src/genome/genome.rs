@@ -1,17 +1,26 @@
-use std::cmp::{max, Ordering};
+use std::{
+    cmp::{max, Ordering},
+    collections::{HashMap, HashSet},
+};
 
-use rand::{thread_rng, Rng};
+use rand::{seq::SliceRandom, thread_rng, Rng};
 
-use super::{connection_gene::ConnectionGene, node_gene::NodeGene};
+use super::{
+    connection_gene::ConnectionGene,
+    node_gene::{NodeGene, NodeType},
+};
 use crate::data_structures::RandomHashSet;
-use crate::neat::{Config, Neat};
+use crate::neat::{Config, Neat, Phase};
 
 #[cfg(test)]
 #[path = "genome_test.rs"]
 mod genome_test;
 
+/// The lowest time constant a node can mutate to
+pub const MIN_TIME_CONSTANT: f32 = 0.01;
+
 /// The genome with it's connections and nodes
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, Debug)]
 pub struct Genome {
     /// All the connections in this genome
     pub connections: RandomHashSet<ConnectionGene>,
@@ -28,6 +37,183 @@ impl Genome {
         }
     }
 
+    /// Get the complexity of this genome, the number of nodes and connections
+    /// ```rust
+    /// use profqu_neat::Neat;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 2, 3);
+    ///
+    /// let mut genome = neat.empty_genome();
+    /// assert_eq!(genome.complexity(), 5);
+    ///
+    /// genome.add_connection(&mut neat, 0, 3);
+    /// assert_eq!(genome.complexity(), 6);
+    /// ```
+    pub fn complexity(&self) -> usize {
+        self.nodes.len() + self.connections.len()
+    }
+
+    #[doc(hidden)]
+    /// Replace every copy of a node in this genome with `node`, matched by innovation number
+    pub fn update_node(&mut self, node: NodeGene) {
+        for existing in &mut self.nodes.data {
+            if *existing == node {
+                *existing = node;
+            }
+        }
+
+        for connection in &mut self.connections.data {
+            if connection.from == node {
+                connection.from = node;
+            }
+            if connection.to == node {
+                connection.to = node;
+            }
+        }
+    }
+
+    /// Split the nodes into the inputs, the bias node, the hidden nodes in the order they have to be calculated and the outputs
+    pub(crate) fn evaluation_order(
+        &self,
+    ) -> (
+        Vec<NodeGene>,
+        Option<NodeGene>,
+        Vec<NodeGene>,
+        Vec<NodeGene>,
+    ) {
+        let nodes_of_type = |node_type: NodeType| -> Vec<NodeGene> {
+            self.nodes
+                .data
+                .iter()
+                .filter(|node| node.node_type == node_type)
+                .copied()
+                .collect()
+        };
+
+        let input_nodes = nodes_of_type(NodeType::Input);
+        let bias = nodes_of_type(NodeType::Bias).first().copied();
+        let hidden_nodes = self.topological_order(nodes_of_type(NodeType::Hidden));
+        let output_nodes = nodes_of_type(NodeType::Output);
+
+        (input_nodes, bias, hidden_nodes, output_nodes)
+    }
+
+    /// Get the innovation numbers of the connections that don't go forward in the evaluation order
+    ///
+    /// These form cycles, which evolution only creates when [`Config::allow_recurrent`] is set.
+    /// Feed-forward networks leave them out, since they need a value that isn't calculated yet.
+    pub(crate) fn recurrent_connections(&self) -> HashSet<u32> {
+        let (input_nodes, bias, hidden_nodes, output_nodes) = self.evaluation_order();
+
+        let positions: HashMap<u32, usize> = input_nodes
+            .iter()
+            .chain(&bias)
+            .chain(&hidden_nodes)
+            .chain(&output_nodes)
+            .enumerate()
+            .map(|(position, node)| (node.innovation_number, position))
+            .collect();
+
+        self.connections
+            .data
+            .iter()
+            .filter(|connection| {
+                positions[&connection.from.innovation_number]
+                    >= positions[&connection.to.innovation_number]
+            })
+            .map(|connection| connection.innovation_number)
+            .collect()
+    }
+
+    #[doc(hidden)]
+    /// Sort 'hidden_nodes' so every node comes after the hidden nodes it gets an enabled connection from
+    ///
+    /// Nodes that don't depend on each other are sorted by their x coordinate.
+    fn topological_order(&self, mut hidden_nodes: Vec<NodeGene>) -> Vec<NodeGene> {
+        hidden_nodes.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+        let mut incoming: HashMap<u32, usize> = hidden_nodes
+            .iter()
+            .map(|node| (node.innovation_number, 0))
+            .collect();
+
+        for connection in &self.connections.data {
+            if connection.enabled && incoming.contains_key(&connection.from.innovation_number) {
+                if let Some(count) = incoming.get_mut(&connection.to.innovation_number) {
+                    *count += 1;
+                }
+            }
+        }
+
+        let mut ordered = Vec::with_capacity(hidden_nodes.len());
+
+        while !hidden_nodes.is_empty() {
+            // Only a recurrent connection can leave no node without inputs, then the lowest x goes first
+            let index = hidden_nodes
+                .iter()
+                .position(|node| incoming[&node.innovation_number] == 0)
+                .unwrap_or(0);
+            let node = hidden_nodes.remove(index);
+
+            for connection in &self.connections.data {
+                if connection.enabled && connection.from == node {
+                    if let Some(count) = incoming.get_mut(&connection.to.innovation_number) {
+                        *count = count.saturating_sub(1);
+                    }
+                }
+            }
+
+            ordered.push(node);
+        }
+
+        ordered
+    }
+
+    #[doc(hidden)]
+    /// Check if a connection from 'from' to 'to' can be added without creating a cycle
+    pub(crate) fn can_connect(&self, from: NodeGene, to: NodeGene) -> bool {
+        if from == to
+            || from.node_type == NodeType::Output
+            || matches!(to.node_type, NodeType::Input | NodeType::Bias)
+        {
+            return false;
+        }
+
+        // Disabled connections count as well, since they can be enabled again
+        let mut reached = HashSet::from([to.innovation_number]);
+        let mut stack = vec![to.innovation_number];
+
+        while let Some(innovation_number) = stack.pop() {
+            if innovation_number == from.innovation_number {
+                return false;
+            }
+
+            for connection in &self.connections.data {
+                if connection.from.innovation_number == innovation_number
+                    && reached.insert(connection.to.innovation_number)
+                {
+                    stack.push(connection.to.innovation_number);
+                }
+            }
+        }
+
+        true
+    }
+
+    #[doc(hidden)]
+    /// Check if a link from 'from' to 'to' may be added by a mutation
+    ///
+    /// With [`Config::allow_recurrent`] it may form a cycle or loop back to the same node,
+    /// otherwise it has to pass [`can_connect`](Genome::can_connect).
+    fn can_link(&self, from: NodeGene, to: NodeGene) -> bool {
+        if Config::global().allow_recurrent {
+            !matches!(to.node_type, NodeType::Input | NodeType::Bias)
+        } else {
+            self.can_connect(from, to)
+        }
+    }
+
     #[doc(hidden)]
     /// Get the highest innovation number of this genome
     fn highest_innov_num(&self) -> u32 {
@@ -236,21 +422,65 @@ impl Genome {
             index1 += 1;
         }
 
+        // The nodes keep the time constants and biases of the fittest genome
+        let parent_nodes: HashMap<u32, NodeGene> = genome1
+            .nodes
+            .data
+            .iter()
+            .map(|node| (node.innovation_number, *node))
+            .collect();
+        let inherit = |node: &mut NodeGene| {
+            if let Some(parent_node) = parent_nodes.get(&node.innovation_number) {
+                *node = *parent_node;
+            }
+        };
+
+        baby.nodes.data.iter_mut().for_each(inherit);
+        for connection in &mut baby.connections.data {
+            inherit(&mut connection.from);
+            inherit(&mut connection.to);
+        }
+
         baby
     }
 
     /// Mutate this genome with one of the following with a certain probability
     ///  - Mutate a new link with [`config.prob_mutate_link`](crate::neat::Config::prob_mutate_link)
     ///  - Mutate a new node with [`config.prob_mutate_node`](crate::neat::Config::prob_mutate_node)
+    ///
+    /// While `neat` is in a [simplifying phase](crate::neat::Phase::Simplifying), links and nodes are deleted
+    /// instead of added, with these probabilities multiplied by the
+    /// [deletion multiplier](crate::neat::PhasedSearch::deletion_multiplier).
+    ///
     ///  - Mutate a weight shift with [`config.prob_mutate_weight_shift`](crate::neat::Config::prob_mutate_weight_shift)
     ///  - Mutate a new random weight with [`config.prob_mutate_weight_random`](crate::neat::Config::prob_mutate_weight_random)
     ///  - Mutate a toggle a link on or off with [`config.prob_mutate_toggle_link`](crate::neat::Config::prob_mutate_toggle_link)
+    ///  - Mutate a time constant shift with [`config.prob_mutate_time_constant`](crate::neat::Config::prob_mutate_time_constant)
+    ///  - Mutate a bias shift with [`config.prob_mutate_bias`](crate::neat::Config::prob_mutate_bias)
+    ///  - Mutate an Izhikevich parameter shift with [`config.prob_mutate_izhikevich`](crate::neat::Config::prob_mutate_izhikevich)
+    ///  - Mutate a Hebbian coefficient shift with [`config.prob_mutate_hebbian`](crate::neat::Config::prob_mutate_hebbian)
     pub fn mutate(&mut self, neat: &mut Neat) {
-        if Config::global().prob_mutate_link > rand::random() {
-            self.mutate_link(neat);
-        }
-        if Config::global().prob_mutate_node > rand::random() {
-            self.mutate_node(neat);
+        match neat.phase() {
+            Phase::Complexifying => {
+                if Config::global().prob_mutate_link > rand::random() {
+                    self.mutate_link(neat);
+                }
+                if Config::global().prob_mutate_node > rand::random() {
+                    self.mutate_node(neat);
+                }
+            }
+            Phase::Simplifying => {
+                let multiplier = neat
+                    .phased_search()
+                    .map_or(1.0, |phased_search| phased_search.deletion_multiplier);
+
+                if Config::global().prob_mutate_link * multiplier > rand::random() {
+                    self.mutate_delete_link();
+                }
+                if Config::global().prob_mutate_node * multiplier > rand::random() {
+                    self.mutate_delete_node();
+                }
+            }
         }
         if Config::global().prob_mutate_weight_shift > rand::random() {
             self.mutate_weight_shift();
@@ -261,6 +491,18 @@ impl Genome {
         if Config::global().prob_mutate_toggle_link > rand::random() {
             self.mutate_link_toggle();
         }
+        if Config::global().prob_mutate_time_constant > rand::random() {
+            self.mutate_time_constant();
+        }
+        if Config::global().prob_mutate_bias > rand::random() {
+            self.mutate_bias();
+        }
+        if Config::global().prob_mutate_izhikevich > rand::random() {
+            self.mutate_izhikevich();
+        }
+        if Config::global().prob_mutate_hebbian > rand::random() {
+            self.mutate_hebbian();
+        }
     }
 
     #[doc(hidden)]
@@ -274,14 +516,12 @@ impl Genome {
             let node1 = *self.nodes.random_element().expect("Nodes array is empty");
             let node2 = *self.nodes.random_element().expect("Nodes array is empty");
 
-            if node1.x == node2.x {
-                continue;
-            }
-
-            let connection = if node1.x < node2.x {
+            let connection = if self.can_link(node1, node2) {
                 ConnectionGene::new(node1, node2)
-            } else {
+            } else if self.can_link(node2, node1) {
                 ConnectionGene::new(node2, node1)
+            } else {
+                continue;
             };
 
             if self.connections.contains(&connection) {
@@ -324,12 +564,20 @@ impl Genome {
                     .expect("Failed to get the replace_index");
             }
 
+            // The node might already be connected in a way that would create a cycle
+            if self.nodes.contains(&middle)
+                && !(self.can_link(from, middle) && self.can_link(middle, to))
+            {
+                return;
+            }
+
             let mut connection1 = neat.get_connection(from, middle);
             let mut connection2 = neat.get_connection(middle, to);
 
             connection1.weight = 1.0;
             connection2.weight = connection.weight;
             connection2.enabled = connection.enabled;
+            connection2.hebbian = connection.hebbian;
 
             self.connections.remove_value(connection);
             self.connections.add(connection1);
@@ -339,6 +587,59 @@ impl Genome {
         }
     }
 
+    #[doc(hidden)]
+    /// Delete a random link and any hidden nodes that are left without connections
+    pub fn mutate_delete_link(&mut self) {
+        if let Some(connection) = self.connections.clone().random_element() {
+            self.connections.remove_value(connection);
+            self.remove_unconnected_nodes();
+        }
+    }
+
+    #[doc(hidden)]
+    /// Delete a random hidden node and all of its connections
+    pub fn mutate_delete_node(&mut self) {
+        let hidden_nodes: Vec<NodeGene> = self
+            .nodes
+            .data
+            .iter()
+            .filter(|node| Genome::is_hidden(node))
+            .copied()
+            .collect();
+
+        if let Some(node) = hidden_nodes.choose(&mut thread_rng()) {
+            for connection in self.connections.data.clone() {
+                if connection.from == *node || connection.to == *node {
+                    self.connections.remove_value(&connection);
+                }
+            }
+
+            self.nodes.remove_value(node);
+        }
+    }
+
+    #[doc(hidden)]
+    /// Remove all hidden nodes that have no connections
+    fn remove_unconnected_nodes(&mut self) {
+        for node in self.nodes.data.clone() {
+            let connected = self
+                .connections
+                .data
+                .iter()
+                .any(|connection| connection.from == node || connection.to == node);
+
+            if Genome::is_hidden(&node) && !connected {
+                self.nodes.remove_value(&node);
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    /// Check if a node is a hidden node
+    fn is_hidden(node: &NodeGene) -> bool {
+        node.node_type == NodeType::Hidden
+    }
+
     #[doc(hidden)]
     /// Get a random range from -constant to constant inclusive
     fn get_random_range(constant: f32) -> f32 {
@@ -364,6 +665,74 @@ impl Genome {
         }
     }
 
+    #[doc(hidden)]
+    /// Shift the time constant of a random hidden or output node, it never goes below [`MIN_TIME_CONSTANT`]
+    pub fn mutate_time_constant(&mut self) {
+        if let Some(mut node) = self.random_calculated_node() {
+            let shift = Genome::get_random_range(Config::global().weight_shift_strength);
+            node.time_constant = (node.time_constant + shift).max(MIN_TIME_CONSTANT);
+            self.update_node(node);
+        }
+    }
+
+    #[doc(hidden)]
+    /// Shift the bias of a random hidden or output node
+    pub fn mutate_bias(&mut self) {
+        if let Some(mut node) = self.random_calculated_node() {
+            node.bias += Genome::get_random_range(Config::global().weight_shift_strength);
+            self.update_node(node);
+        }
+    }
+
+    #[doc(hidden)]
+    /// Shift one of the Hebbian coefficients of a random link, the learning rate never goes below zero
+    pub fn mutate_hebbian(&mut self) {
+        if let Some(connection) = self.connections.random_element() {
+            let shift = Genome::get_random_range(Config::global().weight_shift_strength);
+            let hebbian = &mut connection.hebbian;
+
+            match thread_rng().gen_range(0..5) {
+                0 => hebbian.a += shift,
+                1 => hebbian.b += shift,
+                2 => hebbian.c += shift,
+                3 => hebbian.d += shift,
+                _ => hebbian.learning_rate = (hebbian.learning_rate + shift).max(0.0),
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    /// Shift one of the Izhikevich parameters of a random hidden or output node
+    ///
+    /// The shift is scaled to the usual range of the parameter and `a` never goes below zero.
+    pub fn mutate_izhikevich(&mut self) {
+        if let Some(mut node) = self.random_calculated_node() {
+            let shift = Genome::get_random_range(Config::global().weight_shift_strength);
+            let parameters = &mut node.izhikevich;
+
+            match thread_rng().gen_range(0..4) {
+                0 => parameters.a = (parameters.a + 0.1 * shift).max(0.0),
+                1 => parameters.b += 0.1 * shift,
+                2 => parameters.c += 10.0 * shift,
+                _ => parameters.d += 5.0 * shift,
+            }
+
+            self.update_node(node);
+        }
+    }
+
+    /// Get a random node that isn't an input or the bias
+    fn random_calculated_node(&self) -> Option<NodeGene> {
+        let nodes: Vec<&NodeGene> = self
+            .nodes
+            .data
+            .iter()
+            .filter(|node| matches!(node.node_type, NodeType::Hidden | NodeType::Output))
+            .collect();
+
+        nodes.choose(&mut thread_rng()).map(|node| **node)
+    }
+
     #[doc(hidden)]
     /// Toggle the enabled status of a link
     pub fn mutate_link_toggle(&mut self) {
@@ -373,6 +742,20 @@ impl Genome {
     }
 }
 
+/// Genomes are equal when they have the same nodes and connections with the [same values](ConnectionGene::same_values)
+impl PartialEq for Genome {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes
+            && self.connections.len() == other.connections.len()
+            && self
+                .connections
+                .data
+                .iter()
+                .zip(&other.connections.data)
+                .all(|(connection, other)| connection.same_values(other))
+    }
+}
+
 impl Default for Genome {
     fn default() -> Self {
         Self::new()
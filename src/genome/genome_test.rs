@@ -1,4 +1,6 @@
-use crate::genome::Genome;
+use crate::calculations::Calculator;
+use crate::data_structures::RandomHashSet;
+use crate::genome::{Genome, Hebbian, Izhikevich, NodeType, MIN_TIME_CONSTANT};
 use crate::neat::Config;
 use crate::Neat;
 
@@ -319,3 +321,266 @@ fn mutate_link_toggle() {
         previous2 = current2;
     }
 }
+
+#[test]
+fn mutate_delete_link() {
+    Neat::test_config();
+    let mut neat = Neat::new(2, 3, 90);
+
+    let mut genome = neat.empty_genome();
+
+    genome.add_connection(&mut neat, 0, 3);
+    genome.mutate_node(&mut neat);
+
+    assert_eq!(genome.nodes.len(), 7);
+    assert_eq!(genome.connections.len(), 2);
+
+    genome.mutate_delete_link();
+
+    // The hidden node still has one connection left
+    assert_eq!(genome.nodes.len(), 7);
+    assert_eq!(genome.connections.len(), 1);
+
+    genome.mutate_delete_link();
+
+    // The hidden node is removed together with its last connection
+    assert_eq!(genome.nodes.len(), 6);
+    assert_eq!(genome.connections.len(), 0);
+
+    genome.mutate_delete_link();
+
+    assert_eq!(genome.nodes.len(), 6);
+    assert_eq!(genome.connections.len(), 0);
+}
+
+#[test]
+fn mutate_delete_node() {
+    Neat::test_config();
+    let mut neat = Neat::new(2, 3, 90);
+
+    let mut genome = neat.empty_genome();
+
+    genome.add_connection(&mut neat, 0, 3);
+    genome.add_connection(&mut neat, 1, 4);
+
+    // Without hidden nodes nothing gets deleted
+    genome.mutate_delete_node();
+
+    assert_eq!(genome.nodes.len(), 6);
+    assert_eq!(genome.connections.len(), 2);
+
+    genome.mutate_node(&mut neat);
+
+    assert_eq!(genome.nodes.len(), 7);
+    assert_eq!(genome.connections.len(), 3);
+
+    genome.mutate_delete_node();
+
+    assert_eq!(genome.nodes.len(), 6);
+    assert_eq!(genome.connections.len(), 1);
+}
+
+#[test]
+fn mutate_delete_after_changes() {
+    Neat::test_config();
+    let mut neat = Neat::new(2, 3, 90);
+
+    let mut genome = neat.empty_genome();
+    genome.add_connection(&mut neat, 0, 3);
+    genome.add_connection(&mut neat, 1, 4);
+    genome.mutate_node(&mut neat);
+    genome.mutate_node(&mut neat);
+
+    // The mutations change the genes in place
+    for _ in 0..20 {
+        genome.mutate_weight_shift();
+        genome.mutate_weight_random();
+        genome.mutate_link_toggle();
+        genome.mutate_hebbian();
+    }
+
+    let mut deleted = genome.clone();
+    deleted.mutate_delete_node();
+    assert_eq!(deleted.nodes.len(), genome.nodes.len() - 1);
+    for connection in &deleted.connections.data {
+        assert!(deleted.nodes.contains(&connection.from));
+        assert!(deleted.nodes.contains(&connection.to));
+    }
+    Calculator::new(deleted);
+
+    for connections in (0..genome.connections.len()).rev() {
+        genome.mutate_delete_link();
+        assert_eq!(genome.connections.len(), connections);
+    }
+    assert_eq!(genome.nodes.len(), 6);
+}
+
+#[test]
+fn mutate_link_without_cycles() {
+    Neat::test_config();
+    let mut neat = Neat::new(2, 2, 90);
+
+    let mut genome = neat.empty_genome();
+
+    // Hidden nodes at the same x can still be connected in one direction
+    let hidden1 = neat.create_node(0.5, 0.3);
+    let hidden2 = neat.create_node(0.5, 0.6);
+    genome.nodes.add(hidden1);
+    genome.nodes.add(hidden2);
+    genome
+        .connections
+        .add(neat.get_connection(hidden1, hidden2));
+
+    assert!(!genome.can_connect(hidden2, hidden1));
+    assert!(!genome.can_connect(hidden1, hidden1));
+    assert!(!genome.can_connect(genome.nodes.data[3], hidden1));
+    assert!(!genome.can_connect(hidden1, genome.nodes.data[2]));
+    assert!(genome.can_connect(genome.nodes.data[0], hidden2));
+
+    for _ in 0..100 {
+        genome.mutate_link(&mut neat);
+    }
+
+    let (_, _, hidden_nodes, _) = genome.evaluation_order();
+
+    for connection in &genome.connections.data {
+        assert_ne!(connection.from.node_type, NodeType::Output);
+        assert_ne!(connection.to.node_type, NodeType::Input);
+        assert_ne!(connection.to.node_type, NodeType::Bias);
+
+        let from = hidden_nodes
+            .iter()
+            .position(|node| *node == connection.from);
+        let to = hidden_nodes.iter().position(|node| *node == connection.to);
+        if let (Some(from), Some(to)) = (from, to) {
+            assert!(from < to);
+        }
+    }
+}
+
+#[test]
+fn mutate_node_without_cycles() {
+    Neat::test_config();
+    let mut neat = Neat::new(1, 1, 90);
+
+    let mut genome = neat.empty_genome();
+    let input = genome.nodes.data[0];
+    let hidden1 = neat.create_node(0.5, 0.3);
+    let hidden2 = neat.create_node(0.5, 0.6);
+
+    // Splitting 'input -> hidden1' creates 'middle', which will be reused every time it's split again
+    genome.nodes.add(hidden1);
+    genome.connections.add(neat.get_connection(input, hidden1));
+    genome.mutate_node(&mut neat);
+    let middle = genome.nodes.data[4];
+
+    // Splitting it again would connect 'middle' to 'hidden1', which leads back to 'middle'
+    genome.nodes.add(hidden2);
+    genome.connections = RandomHashSet::new();
+    genome.connections.add(neat.get_connection(input, hidden1));
+    genome
+        .connections
+        .add(neat.get_connection(hidden1, hidden2));
+    genome.connections.add(neat.get_connection(hidden2, middle));
+
+    for _ in 0..100 {
+        let mut mutated = genome.clone();
+        mutated.mutate_node(&mut neat);
+
+        let (_, _, hidden_nodes, _) = mutated.evaluation_order();
+        for connection in &mutated.connections.data {
+            let from = hidden_nodes
+                .iter()
+                .position(|node| *node == connection.from);
+            let to = hidden_nodes.iter().position(|node| *node == connection.to);
+            if let (Some(from), Some(to)) = (from, to) {
+                assert!(from < to);
+            }
+        }
+    }
+}
+
+#[test]
+fn mutate_time_constant() {
+    Neat::test_config();
+    let mut neat = Neat::new(1, 1, 10);
+
+    let mut genome = neat.empty_genome();
+    genome.add_connection(&mut neat, 0, 2);
+
+    for _ in 0..100 {
+        genome.mutate_time_constant();
+        genome.mutate_bias();
+
+        // The copy of the output in the connection is updated too
+        let output = genome.nodes.data[2];
+        assert!(output.time_constant >= MIN_TIME_CONSTANT);
+        assert_eq!(
+            genome.connections.data[0].to.time_constant,
+            output.time_constant
+        );
+        assert_eq!(genome.connections.data[0].to.bias, output.bias);
+    }
+
+    // Inputs and the bias are never mutated
+    assert_eq!(genome.nodes.data[0].time_constant, 1.0);
+    assert_eq!(genome.nodes.data[1].bias, 0.0);
+    assert_ne!(genome.nodes.data[2].time_constant, 1.0);
+
+    // The node parameters are inherited from the fitter parent
+    let empty = neat.empty_genome();
+    let baby = Genome::crossover(&mut neat, &genome, &empty);
+    let output = genome.nodes.data[2];
+    assert!(baby
+        .nodes
+        .data
+        .iter()
+        .any(|node| node.innovation_number == output.innovation_number
+            && node.time_constant == output.time_constant));
+    assert_eq!(baby.connections.data[0].to.bias, output.bias);
+}
+
+#[test]
+fn mutate_izhikevich() {
+    Neat::test_config();
+    let mut neat = Neat::new(1, 1, 10);
+
+    let mut genome = neat.empty_genome();
+    genome.add_connection(&mut neat, 0, 2);
+
+    for _ in 0..100 {
+        genome.mutate_izhikevich();
+
+        let output = genome.nodes.data[2];
+        assert!(output.izhikevich.a >= 0.0);
+        assert_eq!(genome.connections.data[0].to.izhikevich, output.izhikevich);
+    }
+
+    assert_eq!(genome.nodes.data[0].izhikevich, Izhikevich::REGULAR_SPIKING);
+    assert_ne!(genome.nodes.data[2].izhikevich, Izhikevich::REGULAR_SPIKING);
+}
+
+#[test]
+fn mutate_hebbian() {
+    Neat::test_config();
+    let mut neat = Neat::new(1, 1, 10);
+
+    let mut genome = neat.empty_genome();
+    genome.add_connection(&mut neat, 0, 2);
+
+    for _ in 0..100 {
+        genome.mutate_hebbian();
+        assert!(genome.connections.data[0].hebbian.learning_rate >= 0.0);
+    }
+    assert_ne!(genome.connections.data[0].hebbian, Hebbian::default());
+
+    // Splitting the link keeps its coefficients on the second half
+    let hebbian = genome.connections.data[0].hebbian;
+    genome.mutate_node(&mut neat);
+    assert!(genome
+        .connections
+        .data
+        .iter()
+        .any(|connection| connection.to.node_type == NodeType::Output
+            && connection.hebbian == hebbian));
+}
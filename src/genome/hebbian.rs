@@ -0,0 +1,32 @@
+/// The coefficients of the ABCD Hebbian rule of a connection, used when [plasticity](crate::neat::Config::plasticity) is enabled
+///
+/// After every calculation the weight changes by `learning_rate * (a * pre * post + b * pre + c * post + d)`,
+/// where `pre` is the output of the node the connection comes from and `post` the output of the node it goes to.
+///
+/// The default has a learning rate of zero, so the weight never changes.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Hebbian {
+    /// The coefficient of the correlation between the two nodes
+    pub a: f32,
+    /// The coefficient of the output of the node the connection comes from
+    pub b: f32,
+    /// The coefficient of the output of the node the connection goes to
+    pub c: f32,
+    /// The constant change
+    pub d: f32,
+    /// How fast the weight changes
+    pub learning_rate: f32,
+}
+
+impl Hebbian {
+    /// Create new coefficients
+    pub const fn new(a: f32, b: f32, c: f32, d: f32, learning_rate: f32) -> Self {
+        Self {
+            a,
+            b,
+            c,
+            d,
+            learning_rate,
+        }
+    }
+}
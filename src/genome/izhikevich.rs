@@ -0,0 +1,39 @@
+/// The parameters of an Izhikevich spiking neuron, used by a [`SpikingNetwork`](crate::calculations::SpikingNetwork)
+///
+/// The membrane potential `v` and the recovery `u` follow `dv/dt = 0.04v² + 5v + 140 - u + I`
+/// and `du/dt = a(bv - u)`. When `v` reaches 30 mV the neuron spikes, `v` is reset to `c` and `d` is added to `u`.
+///
+/// The default is a regular spiking neuron.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Izhikevich {
+    /// The time scale of the recovery, smaller is slower
+    pub a: f32,
+    /// How sensitive the recovery is to the membrane potential
+    pub b: f32,
+    /// The membrane potential after a spike in mV
+    pub c: f32,
+    /// How much the recovery increases after a spike
+    pub d: f32,
+}
+
+impl Izhikevich {
+    /// A regular spiking neuron, which adapts to a constant input by spiking slower
+    pub const REGULAR_SPIKING: Self = Self::new(0.02, 0.2, -65.0, 8.0);
+    /// An intrinsically bursting neuron, which starts with a burst of spikes
+    pub const INTRINSICALLY_BURSTING: Self = Self::new(0.02, 0.2, -55.0, 4.0);
+    /// A chattering neuron, which keeps spiking in bursts
+    pub const CHATTERING: Self = Self::new(0.02, 0.2, -50.0, 2.0);
+    /// A fast spiking neuron, which spikes quickly without adapting
+    pub const FAST_SPIKING: Self = Self::new(0.1, 0.2, -65.0, 2.0);
+
+    /// Create new parameters
+    pub const fn new(a: f32, b: f32, c: f32, d: f32) -> Self {
+        Self { a, b, c, d }
+    }
+}
+
+impl Default for Izhikevich {
+    fn default() -> Self {
+        Self::REGULAR_SPIKING
+    }
+}
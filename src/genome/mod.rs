@@ -1,10 +1,21 @@
 //! The module this crate uses to create and mutate/evolve genomes
 
 mod connection_gene;
+mod dot;
 #[allow(clippy::module_inception)]
 mod genome;
+mod hebbian;
+mod izhikevich;
 mod node_gene;
+mod prune;
+mod rust_source;
+mod svg;
 
 pub use connection_gene::ConnectionGene;
-pub use genome::Genome;
-pub use node_gene::NodeGene;
+pub use genome::{Genome, MIN_TIME_CONSTANT};
+pub use hebbian::Hebbian;
+pub use izhikevich::Izhikevich;
+pub use node_gene::{NodeGene, NodeType};
+pub use prune::PruneReport;
+
+pub(crate) use dot::write_dot;
@@ -1,14 +1,38 @@
 use std::{fmt::Debug, hash::Hash};
 
+use super::Izhikevich;
+
+/// The role of a node gene in the network
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum NodeType {
+    /// A node that gets one of the inputs
+    Input,
+    /// The node that always outputs 1.0
+    Bias,
+    /// A node between the inputs and the outputs
+    #[default]
+    Hidden,
+    /// A node that gives one of the outputs
+    Output,
+}
+
 /// The node gene of some genome
 #[derive(Clone, Copy)]
 pub struct NodeGene {
     /// The innovation number of this gene
     pub innovation_number: u32,
-    /// The x coordinate of this gene, used for checking that we aren't creating recurrent connections and drawing the neural network
+    /// The type of this gene
+    pub node_type: NodeType,
+    /// The x coordinate of this gene, used for drawing the neural network
     pub x: f32,
     /// The y coordinate of this gene, used for drawing the neural network
     pub y: f32,
+    /// How slowly the state of this node follows its inputs in a [`Ctrnn`](crate::calculations::Ctrnn)
+    pub time_constant: f32,
+    /// The bias that is added to the state of this node in a [`Ctrnn`](crate::calculations::Ctrnn)
+    pub bias: f32,
+    /// The parameters of this node as a spiking neuron in a [`SpikingNetwork`](crate::calculations::SpikingNetwork)
+    pub izhikevich: Izhikevich,
 }
 
 impl NodeGene {
@@ -16,8 +40,12 @@ impl NodeGene {
     pub fn new(innovation_number: u32) -> Self {
         Self {
             innovation_number,
+            node_type: NodeType::Hidden,
             x: 0.0,
             y: 0.0,
+            time_constant: 1.0,
+            bias: 0.0,
+            izhikevich: Izhikevich::default(),
         }
     }
 }
@@ -26,8 +54,8 @@ impl Debug for NodeGene {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Node({:?}, x: {:?}, y: {:?})",
-            self.innovation_number, self.x, self.y
+            "Node({:?}, {:?}, x: {:?}, y: {:?})",
+            self.innovation_number, self.node_type, self.x, self.y
         )
     }
 }
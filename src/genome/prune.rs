@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+
+use super::{Genome, NodeGene};
+use crate::neat::{ActivationFunction, Config};
+
+/// What was removed from a genome by [`Genome::pruned`]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct PruneReport {
+    /// The innovation numbers of the removed connections
+    pub removed_connections: Vec<u32>,
+    /// The innovation numbers of the removed hidden nodes
+    pub removed_nodes: Vec<u32>,
+}
+
+impl Genome {
+    /// Get a copy of this genome without the genes that don't change its outputs
+    ///
+    /// Disabled connections, connections that form a cycle and hidden nodes without a path to an output are removed.
+    /// Hidden nodes that can't be reached from an input or the bias always output
+    /// `activation(0)`, so they are only removed if that is zero. Input and output nodes are always kept.
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 10);
+    ///
+    /// let mut genome = neat.empty_genome();
+    /// genome.add_connection(&mut neat, 0, 3);
+    /// genome.add_connection(&mut neat, 1, 3);
+    /// genome.connections.data[1].enabled = false;
+    ///
+    /// let (pruned, report) = genome.pruned();
+    /// assert_eq!(pruned.connections.len(), 1);
+    /// assert_eq!(report.removed_connections, vec![genome.connections.data[1].innovation_number]);
+    /// assert!(report.removed_nodes.is_empty());
+    /// ```
+    pub fn pruned(&self) -> (Genome, PruneReport) {
+        let (input_nodes, bias, hidden_nodes, output_nodes) = self.evaluation_order();
+
+        let mut useful = self.nodes_reaching(&output_nodes);
+
+        if matches!(Config::global().activation, ActivationFunction::Relu) {
+            let sources: Vec<NodeGene> = input_nodes.into_iter().chain(bias).collect();
+            let reached = self.nodes_reached_from(&sources);
+
+            useful.retain(|innovation_number| reached.contains(innovation_number));
+        }
+
+        let mut pruned = Genome::new();
+        let mut report = PruneReport::default();
+
+        for node in &self.nodes.data {
+            let hidden = hidden_nodes.contains(node);
+
+            if !hidden || useful.contains(&node.innovation_number) {
+                pruned.nodes.add(*node);
+            } else {
+                report.removed_nodes.push(node.innovation_number);
+            }
+        }
+
+        let recurrent = self.recurrent_connections();
+        for connection in &self.connections.data {
+            if connection.enabled
+                && !recurrent.contains(&connection.innovation_number)
+                && pruned.nodes.contains(&connection.from)
+                && pruned.nodes.contains(&connection.to)
+            {
+                pruned.connections.add(*connection);
+            } else {
+                report
+                    .removed_connections
+                    .push(connection.innovation_number);
+            }
+        }
+
+        (pruned, report)
+    }
+
+    /// Get the innovation numbers of all nodes with a path of enabled connections from one of 'sources'
+    fn nodes_reached_from(&self, sources: &[NodeGene]) -> HashSet<u32> {
+        let mut reached: HashSet<u32> = sources.iter().map(|node| node.innovation_number).collect();
+        let mut stack: Vec<u32> = reached.iter().copied().collect();
+
+        while let Some(innovation_number) = stack.pop() {
+            for connection in &self.connections.data {
+                if connection.enabled
+                    && connection.from.innovation_number == innovation_number
+                    && reached.insert(connection.to.innovation_number)
+                {
+                    stack.push(connection.to.innovation_number);
+                }
+            }
+        }
+
+        reached
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PruneReport;
+    use crate::calculations::Calculator;
+    use crate::Neat;
+
+    #[test]
+    fn pruned() {
+        Neat::test_config();
+        let mut neat = Neat::new(2, 1, 10);
+
+        let mut genome = neat.empty_genome();
+        let input1 = genome.nodes.data[0];
+        let input2 = genome.nodes.data[1];
+        let bias = genome.nodes.data[2];
+        let output = genome.nodes.data[3];
+
+        let on_path = neat.create_node(0.5, 0.5);
+        let dead_end = neat.create_node(0.5, 0.2);
+        let constant = neat.create_node(0.5, 0.8);
+        for node in [on_path, dead_end, constant] {
+            genome.nodes.add(node);
+        }
+
+        let mut add = |from, to, weight, enabled| {
+            let mut connection = neat.get_connection(from, to);
+            connection.weight = weight;
+            connection.enabled = enabled;
+            genome.connections.add(connection);
+            connection.innovation_number
+        };
+
+        add(input1, output, 0.7, true);
+        let disabled = add(input2, output, 1.3, false);
+        add(input2, on_path, -0.4, true);
+        add(on_path, output, 0.9, true);
+        add(bias, on_path, 0.2, true);
+        let to_dead_end = add(input1, dead_end, 2.0, true);
+        add(constant, output, -1.1, true);
+
+        let (pruned, report) = genome.pruned();
+
+        assert_eq!(report.removed_connections, vec![disabled, to_dead_end]);
+        assert_eq!(report.removed_nodes, vec![dead_end.innovation_number]);
+
+        // The test config uses a sigmoid, so the constant node still changes the output
+        assert!(pruned.nodes.contains(&constant));
+        assert_eq!(pruned.nodes.len(), 6);
+        assert_eq!(pruned.connections.len(), 5);
+        assert!(pruned.connections.data.iter().all(|c| c.enabled));
+
+        // Pruning an already pruned genome doesn't do anything
+        let (twice, report) = pruned.pruned();
+        assert_eq!(twice, pruned);
+        assert_eq!(report, PruneReport::default());
+
+        let mut original = Calculator::new(genome);
+        let mut minimal = Calculator::new(pruned);
+
+        for inputs in [[0.0, 0.0], [1.0, 0.0], [0.3, -0.8], [-2.0, 5.0]] {
+            assert_eq!(
+                original.calculate(&inputs.to_vec()).unwrap(),
+                minimal.calculate(&inputs.to_vec()).unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::{collections::HashSet, fmt::Write};
+
+use super::{Genome, NodeGene};
+use crate::neat::{ActivationFunction, Config};
+
+impl Genome {
+    /// Generate the source code of a standalone Rust function that calculates the same outputs as this genome
+    ///
+    /// The function has the signature `pub fn fn_name(inputs: [f32; N]) -> [f32; M]`
+    /// and doesn't depend on this crate. All weights are inlined, disabled connections, connections
+    /// that form a cycle and nodes that can't reach any output are left out.
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 10);
+    ///
+    /// let mut genome = neat.empty_genome();
+    /// genome.add_connection(&mut neat, 0, 3);
+    ///
+    /// let source = genome.to_rust_source("network");
+    /// assert!(source.contains("pub fn network(inputs: [f32; 2]) -> [f32; 1] {"));
+    /// assert!(source.contains("let node_4 = activation(1.0 * inputs[0]);"));
+    /// ```
+    pub fn to_rust_source(&self, fn_name: &str) -> String {
+        let (input_nodes, bias, hidden_nodes, output_nodes) = self.evaluation_order();
+
+        let useful = self.nodes_reaching(&output_nodes);
+        let recurrent = self.recurrent_connections();
+
+        let mut body = String::new();
+        let mut uses_inputs = false;
+
+        for node in hidden_nodes
+            .iter()
+            .filter(|node| useful.contains(&node.innovation_number))
+            .chain(&output_nodes)
+        {
+            let mut sum = String::new();
+
+            for connection in &self.connections.data {
+                if !connection.enabled
+                    || connection.to != *node
+                    || recurrent.contains(&connection.innovation_number)
+                {
+                    continue;
+                }
+
+                let source = if Some(connection.from) == bias {
+                    None
+                } else if let Some(index) = input_nodes.iter().position(|n| *n == connection.from) {
+                    uses_inputs = true;
+                    Some(format!("inputs[{index}]"))
+                } else {
+                    Some(format!("node_{}", connection.from.innovation_number))
+                };
+
+                let weight = connection.weight;
+                if sum.is_empty() {
+                    write!(sum, "{weight:?}").expect("Failed to write to string");
+                } else if weight.is_sign_negative() {
+                    write!(sum, " - {:?}", -weight).expect("Failed to write to string");
+                } else {
+                    write!(sum, " + {weight:?}").expect("Failed to write to string");
+                }
+
+                if let Some(source) = source {
+                    write!(sum, " * {source}").expect("Failed to write to string");
+                }
+            }
+
+            if sum.is_empty() {
+                sum.push_str("0.0");
+            }
+
+            writeln!(
+                body,
+                "    let node_{} = activation({sum});",
+                node.innovation_number
+            )
+            .expect("Failed to write to string");
+        }
+
+        let outputs: Vec<String> = output_nodes
+            .iter()
+            .map(|node| format!("node_{}", node.innovation_number))
+            .collect();
+
+        let activation = match Config::global().activation {
+            ActivationFunction::Sigmoid => "1.0 / (1.0 + (-x).exp())",
+            ActivationFunction::Relu => "if x <= 0.0 { 0.0 } else { x }",
+        };
+
+        let mut source = String::new();
+        if !uses_inputs {
+            source.push_str("#[allow(unused_variables)]\n");
+        }
+        writeln!(
+            source,
+            "pub fn {fn_name}(inputs: [f32; {}]) -> [f32; {}] {{",
+            input_nodes.len(),
+            output_nodes.len()
+        )
+        .expect("Failed to write to string");
+        writeln!(
+            source,
+            "    fn activation(x: f32) -> f32 {{\n        {activation}\n    }}\n"
+        )
+        .expect("Failed to write to string");
+        source.push_str(&body);
+        writeln!(source, "    [{}]\n}}", outputs.join(", ")).expect("Failed to write to string");
+
+        source
+    }
+
+    /// Get the innovation numbers of all nodes with a path of enabled connections to one of 'targets'
+    pub(crate) fn nodes_reaching(&self, targets: &[NodeGene]) -> HashSet<u32> {
+        let mut reaching: HashSet<u32> =
+            targets.iter().map(|node| node.innovation_number).collect();
+        let mut stack: Vec<u32> = reaching.iter().copied().collect();
+
+        while let Some(innovation_number) = stack.pop() {
+            for connection in &self.connections.data {
+                if connection.enabled
+                    && connection.to.innovation_number == innovation_number
+                    && reaching.insert(connection.from.innovation_number)
+                {
+                    stack.push(connection.from.innovation_number);
+                }
+            }
+        }
+
+        reaching
+    }
+}
@@ -0,0 +1,142 @@
+use std::fmt::Write;
+
+use super::{Genome, NodeType};
+
+/// The width of the rendered image in pixels
+const WIDTH: f32 = 400.0;
+/// The height of the rendered image in pixels
+const HEIGHT: f32 = 300.0;
+/// The radius of a node in pixels
+const NODE_RADIUS: f32 = 10.0;
+
+impl Genome {
+    /// Render this genome as an SVG image, using the `x` and `y` coordinates of the nodes
+    ///
+    /// Connections are green for positive weights and red for negative weights,
+    /// thicker for larger weights and dashed when disabled.
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 10);
+    ///
+    /// let mut genome = neat.empty_genome();
+    /// genome.add_connection(&mut neat, 0, 3);
+    ///
+    /// let svg = genome.render_svg();
+    /// assert!(svg.starts_with("<svg"));
+    /// assert_eq!(svg.matches("<circle").count(), 4);
+    /// assert_eq!(svg.matches("<line").count(), 1);
+    /// ```
+    pub fn render_svg(&self) -> String {
+        let mut svg = String::new();
+
+        writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{HEIGHT}\" viewBox=\"0 0 {WIDTH} {HEIGHT}\">"
+        )
+        .expect("Failed to write to string");
+        svg.push_str("  <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+
+        // Draw the connections first, so the nodes are drawn on top of them
+        for connection in &self.connections.data {
+            let (x1, y1) = Genome::svg_position(connection.from.x, connection.from.y);
+            let (x2, y2) = Genome::svg_position(connection.to.x, connection.to.y);
+
+            let color = if connection.weight < 0.0 {
+                "red"
+            } else {
+                "forestgreen"
+            };
+            let width = (0.5 + connection.weight.abs()).min(5.0);
+            let dash = if connection.enabled {
+                ""
+            } else {
+                " stroke-dasharray=\"4 2\""
+            };
+
+            writeln!(
+                svg,
+                "  <line x1=\"{x1:.1}\" y1=\"{y1:.1}\" x2=\"{x2:.1}\" y2=\"{y2:.1}\" stroke=\"{color}\" stroke-width=\"{width:.2}\"{dash}/>"
+            )
+            .expect("Failed to write to string");
+            writeln!(
+                svg,
+                "  <text x=\"{:.1}\" y=\"{:.1}\" font-size=\"8\" text-anchor=\"middle\" fill=\"{color}\">{:.2}</text>",
+                (x1 + x2) / 2.0,
+                (y1 + y2) / 2.0 - 2.0,
+                connection.weight
+            )
+            .expect("Failed to write to string");
+        }
+
+        for node in &self.nodes.data {
+            let (x, y) = Genome::svg_position(node.x, node.y);
+
+            let fill = match node.node_type {
+                NodeType::Input => "lightblue",
+                NodeType::Bias => "lightgrey",
+                NodeType::Hidden => "white",
+                NodeType::Output => "lightgreen",
+            };
+
+            writeln!(
+                svg,
+                "  <circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"{NODE_RADIUS}\" fill=\"{fill}\" stroke=\"black\"/>"
+            )
+            .expect("Failed to write to string");
+            writeln!(
+                svg,
+                "  <text x=\"{x:.1}\" y=\"{y:.1}\" font-size=\"10\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>",
+                node.innovation_number
+            )
+            .expect("Failed to write to string");
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Convert the coordinates of a node to a position in the image
+    fn svg_position(x: f32, y: f32) -> (f32, f32) {
+        (x * WIDTH, y * HEIGHT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Neat;
+
+    #[test]
+    fn render_svg() {
+        Neat::test_config();
+        let mut neat = Neat::new(2, 2, 10);
+
+        let mut genome = neat.empty_genome();
+        genome.add_connection(&mut neat, 0, 3);
+        genome.add_connection(&mut neat, 1, 4);
+        genome.mutate_node(&mut neat);
+
+        genome.connections.data[0].weight = -0.5;
+        genome.connections.data[0].enabled = false;
+
+        let svg = genome.render_svg();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+
+        assert_eq!(svg.matches("<circle").count(), genome.nodes.len());
+        assert_eq!(svg.matches("<line").count(), genome.connections.len());
+        assert_eq!(svg.matches("stroke-dasharray").count(), 1);
+        assert_eq!(svg.matches("stroke=\"red\"").count(), 1);
+
+        // The first input node is at x = 0.1 and y = 1 / 3
+        assert!(svg.contains("<circle cx=\"40.0\" cy=\"100.0\" r=\"10\" fill=\"lightblue\""));
+        // The bias node
+        assert!(svg.contains("<circle cx=\"40.0\" cy=\"270.0\" r=\"10\" fill=\"lightgrey\""));
+        // The hidden node is halfway between the nodes of the connection it replaced
+        assert_eq!(svg.matches("fill=\"white\" stroke=\"black\"").count(), 1);
+        assert!(svg.contains(">-0.50</text>"));
+    }
+}
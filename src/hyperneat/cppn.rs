@@ -0,0 +1,73 @@
+use crate::{calculations::Calculator, genome::Genome};
+
+/// The number of inputs of a CPPN, the x and y of the source followed by the x and y of the target
+pub const CPPN_INPUTS: usize = 4;
+
+/// A CPPN that is queried for the connection between two points of a substrate
+pub(super) struct Cppn {
+    calculator: Calculator,
+    weight_output: u32,
+    expression_output: Option<u32>,
+}
+
+impl Cppn {
+    /// Create a CPPN from a genome, the second output decides the expression if `expression_output` is set
+    pub(super) fn new(genome: &Genome, expression_output: bool) -> Result<Self, &'static str> {
+        let (input_nodes, _, _, output_nodes) = genome.evaluation_order();
+
+        if input_nodes.len() != CPPN_INPUTS {
+            return Err("A CPPN needs 4 inputs");
+        }
+        if output_nodes.len() < 1 + usize::from(expression_output) {
+            return Err("The CPPN doesn't have enough outputs");
+        }
+
+        // Plasticity would change the CPPN with every query, so the substrate would depend on the query order
+        let mut calculator = Calculator::new(genome.clone());
+        calculator.set_plasticity(false);
+
+        Ok(Self {
+            calculator,
+            weight_output: output_nodes[0].innovation_number,
+            expression_output: expression_output.then(|| output_nodes[1].innovation_number),
+        })
+    }
+
+    /// Get the weight output before the activation function and whether the connection is expressed
+    pub(super) fn query(&mut self, from: (f32, f32), to: (f32, f32)) -> (f32, bool) {
+        let trace = self
+            .calculator
+            .calculate_traced(&vec![from.0, from.1, to.0, to.1])
+            .expect("The CPPN has 4 inputs");
+
+        let weight = trace[&self.weight_output].sum;
+        let expressed = match self.expression_output {
+            Some(output) => trace[&output].sum > 0.0,
+            None => true,
+        };
+
+        (weight, expressed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{genome::Hebbian, Neat};
+
+    #[test]
+    fn query_without_plasticity() {
+        Neat::test_config();
+        let mut neat = Neat::new(4, 1, 10);
+
+        let mut genome = neat.empty_genome();
+        genome.add_connection(&mut neat, 0, 5);
+        genome.connections.data[0].hebbian = Hebbian::new(1.0, 0.0, 0.0, 0.0, 0.5);
+
+        let mut cppn = Cppn::new(&genome, false).unwrap();
+        assert!(!cppn.calculator.plasticity());
+
+        let first = cppn.query((1.0, 0.5), (0.0, 0.0));
+        assert_eq!(cppn.query((1.0, 0.5), (0.0, 0.0)), first);
+    }
+}
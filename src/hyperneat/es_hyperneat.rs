@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+
+use crate::genome::{Genome, NodeGene, NodeType};
+
+use super::{cppn::Cppn, phenotype::Phenotype, HyperNeat};
+
+/// A position on the substrate with its node gene
+type Point = ((f32, f32), NodeGene);
+
+/// A square of the quadtree with the weight of the CPPN at its center
+struct QuadPoint {
+    x: f32,
+    y: f32,
+    /// Half of the size of the square
+    width: f32,
+    level: u32,
+    weight: f32,
+    children: Vec<QuadPoint>,
+}
+
+impl QuadPoint {
+    /// The variance of the weights of the leaves under this square
+    fn variance(&self) -> f32 {
+        let mut weights = Vec::new();
+        self.leaf_weights(&mut weights);
+
+        let mean = weights.iter().sum::<f32>() / weights.len() as f32;
+        weights
+            .iter()
+            .map(|weight| (weight - mean).powi(2))
+            .sum::<f32>()
+            / weights.len() as f32
+    }
+
+    fn leaf_weights(&self, weights: &mut Vec<f32>) {
+        if self.children.is_empty() {
+            weights.push(self.weight);
+        }
+
+        for child in &self.children {
+            child.leaf_weights(weights);
+        }
+    }
+}
+
+/// Evolvable-substrate `HyperNEAT`, which places the hidden nodes where the CPPN has the most information
+///
+/// Starting from every input, a quadtree is divided over the substrate until the weights the CPPN gives
+/// to the connections from that input stop varying. Hidden nodes are placed at the centers of the squares
+/// in a band of changing weights, and then searched from again for [`iteration_level`](EsHyperNeat::iteration_level)
+/// iterations. The outputs are only connected to the hidden nodes that were found.
+///
+/// The result is an ordinary, [pruned](Genome::pruned) genome, so it can be calculated with a `Calculator`.
+///
+/// # Examples
+///
+/// ```rust
+/// use profqu_neat::Neat;
+/// use profqu_neat::calculations::Calculator;
+/// use profqu_neat::hyperneat::{EsHyperNeat, HyperNeat, Substrate};
+///
+/// Neat::test_config();
+/// let mut neat = Neat::new(4, 1, 10);
+///
+/// let mut cppn = neat.empty_genome();
+/// for _ in 0..20 {
+///     cppn.mutate(&mut neat);
+/// }
+///
+/// let substrate = Substrate::new(vec![(-1.0, -1.0), (1.0, -1.0)], Vec::new(), vec![(0.0, 1.0)]);
+/// let genome = EsHyperNeat::new(HyperNeat::new(substrate)).build(&cppn).unwrap();
+///
+/// let mut calculator = Calculator::new(genome);
+/// assert_eq!(calculator.calculate(&vec![0.5, 1.0]).unwrap().len(), 1);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct EsHyperNeat {
+    /// The inputs and outputs of the substrate and how the weights are queried, the hidden layers are ignored
+    pub hyperneat: HyperNeat,
+    /// The depth the quadtree is always divided to
+    pub initial_depth: u32,
+    /// The maximum depth of the quadtree
+    pub max_depth: u32,
+    /// The variance of the weights in a square it needs to be divided further
+    pub division_threshold: f32,
+    /// Squares with a lower variance than this aren't searched deeper for hidden nodes
+    pub variance_threshold: f32,
+    /// How much the weight has to differ from its neighbors for a hidden node to be placed
+    pub band_threshold: f32,
+    /// The number of times the search is repeated from the hidden nodes that were found
+    pub iteration_level: u32,
+}
+
+impl EsHyperNeat {
+    /// Create a new ES-HyperNEAT with an initial depth of 3, a maximum depth of 5, division and variance thresholds of 0.03,
+    /// a band threshold of 0.3 and 1 iteration
+    pub fn new(hyperneat: HyperNeat) -> Self {
+        Self {
+            hyperneat,
+            initial_depth: 3,
+            max_depth: 5,
+            division_threshold: 0.03,
+            variance_threshold: 0.03,
+            band_threshold: 0.3,
+            iteration_level: 1,
+        }
+    }
+
+    /// Discover the hidden nodes and build the genome of the network the CPPN describes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the CPPN doesn't have 4 inputs, or not enough outputs.
+    pub fn build(&self, cppn: &Genome) -> Result<Genome, &'static str> {
+        let mut cppn = Cppn::new(cppn, self.hyperneat.expression_output)?;
+        let mut phenotype = Phenotype::default();
+        let substrate = &self.hyperneat.substrate;
+
+        let input_nodes = phenotype.add_layer(NodeType::Input, 0.1, substrate.input_nodes.len());
+        let bias = phenotype.add_layer(NodeType::Bias, 0.1, 1)[0];
+        let output_nodes = phenotype.add_layer(NodeType::Output, 0.9, substrate.output_nodes.len());
+        let outputs: Vec<Point> = substrate
+            .output_nodes
+            .iter()
+            .copied()
+            .zip(output_nodes)
+            .collect();
+
+        // The hidden nodes by the bits of their position, and in the order they were found
+        let mut hidden_nodes: HashMap<(u32, u32), Point> = HashMap::new();
+        let mut hidden: Vec<Point> = Vec::new();
+        let mut connections: Vec<(Point, Point)> = Vec::new();
+
+        let mut unexplored: Vec<Point> = substrate
+            .input_nodes
+            .iter()
+            .copied()
+            .zip(input_nodes)
+            .collect();
+
+        for _ in 0..=self.iteration_level {
+            let mut discovered = Vec::new();
+
+            for source in unexplored {
+                for position in self.find_connections(&mut cppn, source.0, true) {
+                    let key = (position.0.to_bits(), position.1.to_bits());
+                    let target = *hidden_nodes.entry(key).or_insert_with(|| {
+                        // The substrate goes from the inputs at the bottom to the outputs at the top
+                        let x = 0.1 + 0.4 * (position.1 + 1.0);
+                        let y = 0.1 + 0.4 * (position.0 + 1.0);
+                        let target = (position, phenotype.add_node(NodeType::Hidden, x, y));
+
+                        discovered.push(target);
+                        target
+                    });
+
+                    connections.push((source, target));
+                }
+            }
+
+            hidden.extend(&discovered);
+            unexplored = discovered;
+        }
+
+        for target in outputs.iter().copied() {
+            for position in self.find_connections(&mut cppn, target.0, false) {
+                if let Some(source) =
+                    hidden_nodes.get(&(position.0.to_bits(), position.1.to_bits()))
+                {
+                    connections.push((*source, target));
+                }
+            }
+        }
+
+        for (source, target) in connections {
+            // Connections between hidden nodes could create a cycle
+            if phenotype.genome.can_connect(source.1, target.1) {
+                self.hyperneat
+                    .query_connection(&mut cppn, &mut phenotype, source, target);
+            }
+        }
+
+        for target in hidden.into_iter().chain(outputs) {
+            self.hyperneat
+                .query_connection(&mut cppn, &mut phenotype, ((0.0, 0.0), bias), target);
+        }
+
+        Ok(phenotype.genome.pruned().0)
+    }
+
+    /// Find the positions a node at `position` connects to, or gets connections from if it isn't `outgoing`
+    fn find_connections(
+        &self,
+        cppn: &mut Cppn,
+        position: (f32, f32),
+        outgoing: bool,
+    ) -> Vec<(f32, f32)> {
+        let mut root = QuadPoint {
+            x: 0.0,
+            y: 0.0,
+            width: 1.0,
+            level: 1,
+            weight: 0.0,
+            children: Vec::new(),
+        };
+        self.divide(cppn, position, outgoing, &mut root);
+
+        let mut positions = Vec::new();
+        self.extract(cppn, position, outgoing, &root, &mut positions);
+        positions
+    }
+
+    /// Divide a square into four, and those again while the weights vary enough
+    fn divide(&self, cppn: &mut Cppn, position: (f32, f32), outgoing: bool, point: &mut QuadPoint) {
+        let half = point.width / 2.0;
+
+        for (x, y) in [(-half, -half), (-half, half), (half, -half), (half, half)] {
+            let (x, y) = (point.x + x, point.y + y);
+
+            point.children.push(QuadPoint {
+                x,
+                y,
+                width: half,
+                level: point.level + 1,
+                weight: Self::weight(cppn, position, (x, y), outgoing),
+                children: Vec::new(),
+            });
+        }
+
+        if point.level < self.initial_depth
+            || (point.level < self.max_depth && point.variance() > self.division_threshold)
+        {
+            for child in &mut point.children {
+                self.divide(cppn, position, outgoing, child);
+            }
+        }
+    }
+
+    /// Collect the centers of the squares in a band, where the weight differs from both neighbors on one axis
+    fn extract(
+        &self,
+        cppn: &mut Cppn,
+        position: (f32, f32),
+        outgoing: bool,
+        point: &QuadPoint,
+        positions: &mut Vec<(f32, f32)>,
+    ) {
+        for child in &point.children {
+            if child.variance() >= self.variance_threshold {
+                self.extract(cppn, position, outgoing, child, positions);
+                continue;
+            }
+
+            let mut difference = |x: f32, y: f32| {
+                (child.weight - Self::weight(cppn, position, (child.x + x, child.y + y), outgoing))
+                    .abs()
+            };
+
+            let horizontal = difference(-point.width, 0.0).min(difference(point.width, 0.0));
+            let vertical = difference(0.0, -point.width).min(difference(0.0, point.width));
+
+            if horizontal.max(vertical) > self.band_threshold {
+                positions.push((child.x, child.y));
+            }
+        }
+    }
+
+    /// The weight of the connection between `position` and `other`, in the direction of the search
+    fn weight(cppn: &mut Cppn, position: (f32, f32), other: (f32, f32), outgoing: bool) -> f32 {
+        if outgoing {
+            cppn.query(position, other).0
+        } else {
+            cppn.query(other, position).0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculations::Calculator, hyperneat::Substrate, Neat};
+
+    #[test]
+    fn build() {
+        Neat::test_config();
+        let mut neat = Neat::new(4, 1, 10);
+
+        // The weight is a narrow band where the x of the source or the target is between 0.0 and 0.2
+        let mut cppn = neat.empty_genome();
+        let (source_x, target_x, bias, output) = (
+            cppn.nodes.data[0],
+            cppn.nodes.data[2],
+            cppn.nodes.data[4],
+            cppn.nodes.data[5],
+        );
+
+        for input in [source_x, target_x] {
+            for (offset, sign) in [(0.0, 1.0), (-10.0, -1.0)] {
+                let hidden = neat.create_node(0.5, 0.5);
+                cppn.nodes.add(hidden);
+
+                let mut step = neat.get_connection(input, hidden);
+                step.weight = 50.0;
+                let mut shift = neat.get_connection(bias, hidden);
+                shift.weight = offset;
+                let mut to_output = neat.get_connection(hidden, output);
+                to_output.weight = sign;
+
+                cppn.connections.add(step);
+                cppn.connections.add(shift);
+                cppn.connections.add(to_output);
+            }
+        }
+
+        let substrate = Substrate::new(
+            vec![(-0.9, -1.0), (0.9, -1.0)],
+            Vec::new(),
+            vec![(0.0, 1.0)],
+        );
+        let es_hyperneat = EsHyperNeat::new(HyperNeat::new(substrate));
+
+        let genome = es_hyperneat.build(&cppn).unwrap();
+        let hidden: Vec<NodeGene> = genome
+            .nodes
+            .data
+            .iter()
+            .filter(|node| node.node_type == NodeType::Hidden)
+            .copied()
+            .collect();
+
+        assert!(!hidden.is_empty());
+        for node in &hidden {
+            // Every hidden node is in the band, the y of the drawing is the x of the substrate
+            assert!(node.y > 0.5 && node.y < 0.58);
+        }
+
+        // Every hidden node reaches the output, so nothing was left to prune
+        assert!(genome.pruned().1.removed_nodes.is_empty());
+
+        let mut calculator = Calculator::new(genome);
+        assert_eq!(calculator.calculate(&vec![1.0, -1.0]).unwrap().len(), 1);
+    }
+}
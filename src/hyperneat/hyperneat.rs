@@ -0,0 +1,220 @@
+use std::iter;
+
+use crate::genome::{Genome, NodeGene, NodeType};
+
+use super::{cppn::Cppn, phenotype::Phenotype, Substrate};
+
+/// The positions on the substrate and the node genes of a layer
+type Layer<'a> = (&'a [(f32, f32)], &'a [NodeGene]);
+
+/// Builds the network of a [`Substrate`] by querying a CPPN for the weight of every connection
+///
+/// Every layer is fully connected to the next one and every hidden and output node gets a connection from the bias.
+/// The weight of a connection is the first output of the CPPN before its activation function.
+///
+/// # Examples
+///
+/// ```rust
+/// use profqu_neat::Neat;
+/// use profqu_neat::calculations::Calculator;
+/// use profqu_neat::hyperneat::{HyperNeat, Substrate};
+///
+/// Neat::test_config();
+/// let mut neat = Neat::new(4, 1, 10);
+///
+/// // The weight is the x coordinate of the source
+/// let mut cppn = neat.empty_genome();
+/// cppn.add_connection(&mut neat, 0, 5);
+///
+/// let substrate = Substrate::new(vec![(-1.0, -1.0), (1.0, -1.0)], Vec::new(), vec![(0.0, 1.0)]);
+/// let genome = HyperNeat::new(substrate).build(&cppn).unwrap();
+///
+/// let mut calculator = Calculator::new(genome);
+/// assert_eq!(calculator.calculate(&vec![0.0, 0.0]).unwrap(), vec![0.5]);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct HyperNeat {
+    /// The coordinates of the nodes of the network
+    pub substrate: Substrate,
+    /// Connections with a weight closer to zero than this aren't created
+    pub weight_threshold: f32,
+    /// The highest absolute weight of a connection, higher weights are clamped
+    pub max_weight: f32,
+    /// Only create connections where the second output of the CPPN is above zero, like in HyperNEAT-LEO
+    pub expression_output: bool,
+}
+
+impl HyperNeat {
+    /// Create a new [`HyperNeat`] with a weight threshold of 0.2, a maximum weight of 3.0 and without an expression output
+    pub fn new(substrate: Substrate) -> Self {
+        Self {
+            substrate,
+            weight_threshold: 0.2,
+            max_weight: 3.0,
+            expression_output: false,
+        }
+    }
+
+    /// Build the genome of the network the CPPN describes on the substrate
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the CPPN doesn't have 4 inputs, or not enough outputs.
+    pub fn build(&self, cppn: &Genome) -> Result<Genome, &'static str> {
+        let mut cppn = Cppn::new(cppn, self.expression_output)?;
+        let mut phenotype = Phenotype::default();
+        let substrate = &self.substrate;
+
+        let input_nodes = phenotype.add_layer(NodeType::Input, 0.1, substrate.input_nodes.len());
+        let bias = phenotype.add_layer(NodeType::Bias, 0.1, 1)[0];
+        let output_nodes = phenotype.add_layer(NodeType::Output, 0.9, substrate.output_nodes.len());
+
+        let layer_count = substrate.hidden_layers.len() + 1;
+        let hidden_layers: Vec<Vec<NodeGene>> = substrate
+            .hidden_layers
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| {
+                let x = 0.1 + 0.8 * (index + 1) as f32 / layer_count as f32;
+                phenotype.add_layer(NodeType::Hidden, x, layer.len())
+            })
+            .collect();
+
+        let layers: Vec<Layer> =
+            iter::once((substrate.input_nodes.as_slice(), input_nodes.as_slice()))
+                .chain(
+                    substrate
+                        .hidden_layers
+                        .iter()
+                        .zip(&hidden_layers)
+                        .map(|(positions, nodes)| (positions.as_slice(), nodes.as_slice())),
+                )
+                .chain(iter::once((
+                    substrate.output_nodes.as_slice(),
+                    output_nodes.as_slice(),
+                )))
+                .collect();
+
+        for pair in layers.windows(2) {
+            let (from_positions, from_nodes) = pair[0];
+            let (to_positions, to_nodes) = pair[1];
+
+            for (to_position, to) in to_positions.iter().zip(to_nodes) {
+                for (from_position, from) in from_positions.iter().zip(from_nodes) {
+                    self.query_connection(
+                        &mut cppn,
+                        &mut phenotype,
+                        (*from_position, *from),
+                        (*to_position, *to),
+                    );
+                }
+
+                self.query_connection(
+                    &mut cppn,
+                    &mut phenotype,
+                    ((0.0, 0.0), bias),
+                    (*to_position, *to),
+                );
+            }
+        }
+
+        Ok(phenotype.genome)
+    }
+
+    /// Add the connection between two nodes if it's expressed and its weight is above the threshold
+    pub(super) fn query_connection(
+        &self,
+        cppn: &mut Cppn,
+        phenotype: &mut Phenotype,
+        from: ((f32, f32), NodeGene),
+        to: ((f32, f32), NodeGene),
+    ) {
+        let (weight, expressed) = cppn.query(from.0, to.0);
+
+        if expressed && weight.abs() > self.weight_threshold {
+            phenotype.connect(
+                from.1,
+                to.1,
+                weight.clamp(-self.max_weight, self.max_weight),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculations::Calculator, Neat};
+
+    fn sigmoid(x: f32) -> f32 {
+        1.0 / (1.0 + (-x).exp())
+    }
+
+    #[test]
+    fn build() {
+        Neat::test_config();
+        let mut neat = Neat::new(4, 1, 10);
+
+        // The weight is the x of the source plus twice the y of the target
+        let mut cppn = neat.empty_genome();
+        cppn.add_connection(&mut neat, 0, 5);
+        cppn.add_connection(&mut neat, 3, 5);
+        cppn.connections.data[1].weight = 2.0;
+
+        let substrate = Substrate::new(
+            vec![(-1.0, -1.0), (0.1, -1.0), (1.0, -1.0)],
+            vec![vec![(0.0, 0.0), (0.0, 0.5)]],
+            vec![(0.0, 2.0)],
+        );
+        let mut hyperneat = HyperNeat::new(substrate);
+        hyperneat.max_weight = 4.0;
+
+        let genome = hyperneat.build(&cppn).unwrap();
+        assert_eq!(genome.nodes.len(), 7);
+
+        // The connections from the input at x 0.1 and from the bias to the first hidden node
+        // and from the first input to the second hidden node are too weak
+        let weights: Vec<f32> = genome.connections.data.iter().map(|c| c.weight).collect();
+        assert_eq!(weights, vec![-1.0, 1.0, 1.1, 2.0, 1.0, 4.0, 4.0, 4.0]);
+
+        let mut calculator = Calculator::new(genome);
+        let first = sigmoid(-1.0 + 1.0);
+        let second = sigmoid(1.1 + 2.0 + 1.0);
+        assert_eq!(
+            calculator.calculate(&vec![1.0, 1.0, 1.0]).unwrap(),
+            vec![sigmoid(4.0 * first + 4.0 * second + 4.0)]
+        );
+    }
+
+    #[test]
+    fn expression_output() {
+        Neat::test_config();
+        let mut neat = Neat::new(4, 2, 10);
+
+        // The weight is 1.0 and connections are only expressed to targets with a positive y
+        let mut cppn = neat.empty_genome();
+        cppn.add_connection(&mut neat, 4, 5);
+        cppn.add_connection(&mut neat, 3, 6);
+
+        let substrate = Substrate::new(
+            vec![(0.0, -1.0)],
+            Vec::new(),
+            vec![(-1.0, 1.0), (1.0, -1.0)],
+        );
+        let mut hyperneat = HyperNeat::new(substrate);
+
+        assert_eq!(hyperneat.build(&cppn).unwrap().connections.len(), 4);
+
+        hyperneat.expression_output = true;
+        assert_eq!(hyperneat.build(&cppn).unwrap().connections.len(), 2);
+
+        assert!(HyperNeat::new(Substrate::default())
+            .build(&neat.empty_genome())
+            .is_ok());
+        neat.reset(3, 1, 10);
+        assert_eq!(
+            HyperNeat::new(Substrate::default()).build(&neat.empty_genome()),
+            Err("A CPPN needs 4 inputs")
+        );
+    }
+}
@@ -0,0 +1,18 @@
+//! `HyperNEAT`, where evolved genomes are CPPNs that paint the weights of a much larger network
+//!
+//! A CPPN is a genome with [`CPPN_INPUTS`] inputs, the coordinates of the source and the target of a connection,
+//! created with for example `Neat::new(4, 1, 150)`. [`HyperNeat`] queries it for every connection between
+//! the layers of a [`Substrate`] and builds an ordinary genome, which can be calculated with a `Calculator`.
+//! [`EsHyperNeat`] discovers where to place the hidden nodes instead of using the hidden layers of the substrate.
+
+mod cppn;
+mod es_hyperneat;
+#[allow(clippy::module_inception)]
+mod hyperneat;
+mod phenotype;
+mod substrate;
+
+pub use cppn::CPPN_INPUTS;
+pub use es_hyperneat::EsHyperNeat;
+pub use hyperneat::HyperNeat;
+pub use substrate::Substrate;
@@ -0,0 +1,36 @@
+use crate::genome::{ConnectionGene, Genome, NodeGene, NodeType};
+
+/// Builds the genome of the network a CPPN describes
+#[derive(Default)]
+pub(super) struct Phenotype {
+    pub(super) genome: Genome,
+}
+
+impl Phenotype {
+    /// Add a layer of nodes at the same x, spread out over y
+    pub(super) fn add_layer(&mut self, node_type: NodeType, x: f32, size: usize) -> Vec<NodeGene> {
+        (0..size)
+            .map(|index| self.add_node(node_type, x, (index + 1) as f32 / (size + 1) as f32))
+            .collect()
+    }
+
+    /// Add a node that is drawn at x and y
+    pub(super) fn add_node(&mut self, node_type: NodeType, x: f32, y: f32) -> NodeGene {
+        let mut node = NodeGene::new(self.genome.nodes.len() as u32 + 1);
+        node.node_type = node_type;
+        node.x = x;
+        node.y = y;
+
+        self.genome.nodes.add(node);
+        node
+    }
+
+    /// Add a connection between two nodes of this phenotype
+    pub(super) fn connect(&mut self, from: NodeGene, to: NodeGene, weight: f32) {
+        let mut connection = ConnectionGene::new(from, to);
+        connection.innovation_number = self.genome.connections.len() as u32 + 1;
+        connection.weight = weight;
+
+        self.genome.connections.add(connection);
+    }
+}
@@ -0,0 +1,27 @@
+/// The coordinates of the nodes of the network a CPPN is queried for
+///
+/// The coordinates are usually between -1.0 and 1.0. The bias is queried at `(0.0, 0.0)`.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Substrate {
+    /// The coordinates of the input nodes
+    pub input_nodes: Vec<(f32, f32)>,
+    /// The coordinates of the nodes of every hidden layer
+    pub hidden_layers: Vec<Vec<(f32, f32)>>,
+    /// The coordinates of the output nodes
+    pub output_nodes: Vec<(f32, f32)>,
+}
+
+impl Substrate {
+    /// Create a new substrate
+    pub fn new(
+        input_nodes: Vec<(f32, f32)>,
+        hidden_layers: Vec<Vec<(f32, f32)>>,
+        output_nodes: Vec<(f32, f32)>,
+    ) -> Self {
+        Self {
+            input_nodes,
+            hidden_layers,
+            output_nodes,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use super::math;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// The activation function enum to determine what activation function you're using
+pub enum ActivationFunction {
+    /// The sigmoid activation function
+    Sigmoid,
+    /// The `ReLu` activation function
+    Relu,
+}
+
+impl ActivationFunction {
+    /// Apply this activation function to 'input'
+    pub fn apply(self, input: f32) -> f32 {
+        match self {
+            ActivationFunction::Sigmoid => 1.0 / (1.0 + math::exp(-input)),
+            ActivationFunction::Relu => {
+                if input <= 0.0 {
+                    0.0
+                } else {
+                    input
+                }
+            }
+        }
+    }
+}
+
+impl From<&str> for ActivationFunction {
+    fn from(value: &str) -> Self {
+        match value {
+            "relu" => ActivationFunction::Relu,
+            "sigmoid" => ActivationFunction::Sigmoid,
+            _ => panic!("Wrong activation input"),
+        }
+    }
+}
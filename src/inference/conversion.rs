@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use super::{Layout, Network};
+use crate::genome::Genome;
+use crate::neat::Config;
+
+impl Network {
+    /// Create a network that calculates the same outputs as `genome`
+    ///
+    /// The genome is [pruned](Genome::pruned) first and the activation function is taken from the config.
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    /// use profqu_neat::inference::Network;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 10);
+    ///
+    /// let mut genome = neat.empty_genome();
+    /// genome.add_connection(&mut neat, 0, 3);
+    ///
+    /// let bytes = Network::from_genome(&genome).to_bytes();
+    ///
+    /// // This part also works without std
+    /// let network = Network::from_bytes(&bytes).unwrap();
+    /// let outputs = network.calculate(&[1.0, 0.0]).unwrap();
+    /// assert!((outputs[0] - 0.731_058_6).abs() < 1e-6);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the genome has more than `u16::MAX` nodes
+    pub fn from_genome(genome: &Genome) -> Self {
+        Self {
+            activation: Config::global().activation,
+            layout: Layout::from_genome(genome),
+        }
+    }
+}
+
+impl Layout<f32> {
+    /// Create the layout of `genome` after [pruning](Genome::pruned) it
+    ///
+    /// # Panics
+    ///
+    /// Panics if the genome has more than `u16::MAX` nodes
+    pub(crate) fn from_genome(genome: &Genome) -> Self {
+        let (genome, _) = genome.pruned();
+        let (input_nodes, bias, hidden_nodes, output_nodes) = genome.evaluation_order();
+
+        // The inputs and the bias come first, then the nodes in the order they are calculated
+        let mut indices: HashMap<u32, u16> = HashMap::new();
+        let mut add_index = |innovation_number: u32| {
+            let index = u16::try_from(indices.len()).expect("Too many nodes");
+            indices.insert(innovation_number, index);
+        };
+
+        for node in &input_nodes {
+            add_index(node.innovation_number);
+        }
+        // The bias value is always there, even if the genome doesn't have a bias node
+        add_index(bias.map_or(0, |bias| bias.innovation_number));
+        for node in hidden_nodes.iter().chain(&output_nodes) {
+            add_index(node.innovation_number);
+        }
+
+        let mut layout = Self {
+            input_size: input_nodes.len(),
+            starts: Vec::new(),
+            sources: Vec::new(),
+            weights: Vec::new(),
+            outputs: output_nodes
+                .iter()
+                .map(|node| indices[&node.innovation_number])
+                .collect(),
+        };
+
+        for node in hidden_nodes.iter().chain(&output_nodes) {
+            layout.starts.push(layout.weights.len());
+
+            for connection in &genome.connections.data {
+                if connection.to == *node {
+                    layout
+                        .sources
+                        .push(indices[&connection.from.innovation_number]);
+                    layout.weights.push(connection.weight);
+                }
+            }
+        }
+
+        layout
+    }
+
+    /// Convert every weight with `convert`
+    pub(crate) fn map_weights<W>(self, convert: impl Fn(f32) -> W) -> Layout<W> {
+        Layout {
+            input_size: self.input_size,
+            starts: self.starts,
+            sources: self.sources,
+            weights: self.weights.into_iter().map(convert).collect(),
+            outputs: self.outputs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::{calculations::Calculator, Neat};
+
+    #[test]
+    fn from_genome() {
+        Neat::test_config();
+        let mut neat = Neat::new(4, 3, 10);
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..20 {
+            let mut genome = neat.empty_genome();
+            for _ in 0..30 {
+                genome.mutate_link(&mut neat);
+                genome.mutate_node(&mut neat);
+                genome.mutate_weight_random();
+                genome.mutate_link_toggle();
+            }
+
+            let network = Network::from_genome(&genome);
+            let bytes = network.to_bytes();
+            assert_eq!(Network::from_bytes(&bytes), Ok(network.clone()));
+
+            let mut calculator = Calculator::new(genome);
+            for _ in 0..20 {
+                let inputs: Vec<f32> = (0..4).map(|_| rng.gen_range(-1.0..1.0)).collect();
+
+                let expected = calculator.calculate(&inputs).unwrap();
+                let outputs = network.calculate(&inputs).unwrap();
+
+                for (expected, output) in expected.iter().zip(outputs) {
+                    assert!((expected - output).abs() < 1e-5);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_bytes_invalid() {
+        Neat::test_config();
+        let mut neat = Neat::new(2, 1, 10);
+
+        let mut genome = neat.empty_genome();
+        genome.add_connection(&mut neat, 0, 3);
+
+        let bytes = Network::from_genome(&genome).to_bytes();
+
+        assert!(Network::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Network::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+        assert!(Network::from_bytes(b"ONNX").is_err());
+
+        // A connection from the output itself
+        let mut cyclic = bytes.clone();
+        cyclic[14] = 3;
+        assert_eq!(
+            Network::from_bytes(&cyclic),
+            Err("Connection from a value that isn't calculated yet")
+        );
+    }
+}
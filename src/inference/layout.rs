@@ -0,0 +1,76 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The connections of a network in the order the nodes are calculated, with weights of type `W`
+///
+/// The values are the inputs, the bias and then every calculated node,
+/// so a node can only use the values that are calculated before it.
+/// It's shared by [`Network`](super::Network) and [`QuantizedNetwork`](crate::quantized::QuantizedNetwork),
+/// which only differ in their numeric types.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Layout<W> {
+    pub(crate) input_size: usize,
+    /// For every calculated node, the index of its first connection
+    pub(crate) starts: Vec<usize>,
+    /// The index of the value every connection comes from
+    pub(crate) sources: Vec<u16>,
+    pub(crate) weights: Vec<W>,
+    /// The indices of the values of the output nodes
+    pub(crate) outputs: Vec<u16>,
+}
+
+impl<W: Copy> Layout<W> {
+    /// Get the indices of the connections to a calculated node
+    pub(crate) fn connections(&self, node: usize) -> Range<usize> {
+        let end = self
+            .starts
+            .get(node + 1)
+            .copied()
+            .unwrap_or(self.weights.len());
+
+        self.starts[node]..end
+    }
+
+    /// Calculate the values of the outputs
+    ///
+    /// Every node starts with `zero`, `add` adds a weighted value to its sum and `activate` turns the sum into its value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the number of inputs aren't equal to the number of input nodes.
+    pub(crate) fn calculate<V: Copy, S: Copy>(
+        &self,
+        inputs: &[V],
+        bias: V,
+        zero: S,
+        add: impl Fn(S, W, V) -> S,
+        activate: impl Fn(S) -> V,
+    ) -> Result<Vec<V>, &'static str> {
+        if inputs.len() != self.input_size {
+            return Err("Number of inputs aren't equal to number of input nodes");
+        }
+
+        let mut values = Vec::with_capacity(self.input_size + 1 + self.starts.len());
+        values.extend_from_slice(inputs);
+        values.push(bias);
+
+        for node in 0..self.starts.len() {
+            let mut sum = zero;
+            for index in self.connections(node) {
+                sum = add(
+                    sum,
+                    self.weights[index],
+                    values[self.sources[index] as usize],
+                );
+            }
+
+            values.push(activate(sum));
+        }
+
+        Ok(self
+            .outputs
+            .iter()
+            .map(|index| values[*index as usize])
+            .collect())
+    }
+}
@@ -0,0 +1,48 @@
+//! Float functions that are only in `std`, approximated with `core` operations
+
+use core::f32::consts::LOG2_E;
+
+/// ln(2) split in a part with few significant bits and the rest, so `n * LN_2_HIGH` is exact
+const LN_2_HIGH: f32 = 0.693_145_75;
+const LN_2_LOW: f32 = 1.428_606_8e-6;
+
+/// Calculate `e^x` with a relative error of about `1e-7`
+pub(crate) fn exp(x: f32) -> f32 {
+    if x > 88.0 {
+        return f32::INFINITY;
+    }
+    if x < -87.3 {
+        return 0.0;
+    }
+
+    // e^x = 2^n * e^r with |r| <= ln(2) / 2
+    let scaled = x * LOG2_E;
+    let n = (scaled + if scaled < 0.0 { -0.5 } else { 0.5 }) as i32;
+    let r = (x - n as f32 * LN_2_HIGH) - n as f32 * LN_2_LOW;
+
+    let e_r = 1.0
+        + r * (1.0
+            + r * (0.5 + r * (1.0 / 6.0 + r * (1.0 / 24.0 + r * (1.0 / 120.0 + r / 720.0)))));
+    let two_n = f32::from_bits(((n + 127) as u32) << 23);
+
+    e_r * two_n
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    #[test]
+    fn exp() {
+        for step in -1000..=1000 {
+            let x = step as f32 * 0.087;
+            let expected = x.exp();
+
+            assert!(
+                ((super::exp(x) - expected) / expected).abs() < 1e-6,
+                "e^{x}"
+            );
+        }
+
+        assert_eq!(super::exp(100.0), f32::INFINITY);
+        assert_eq!(super::exp(-100.0), 0.0);
+    }
+}
@@ -0,0 +1,16 @@
+//! Calculate the outputs of evolved networks on devices without `std`
+//!
+//! This module only needs `core` and `alloc`, so it's available when the crate is built without the `std` feature.
+//! Networks are created from genomes with `Network::from_genome` on a machine with `std`,
+//! serialized with [`Network::to_bytes`] and loaded with [`Network::from_bytes`] on the device.
+
+mod activation;
+#[cfg(feature = "std")]
+mod conversion;
+mod layout;
+mod math;
+mod network;
+
+pub use activation::ActivationFunction;
+pub(crate) use layout::Layout;
+pub use network::Network;
@@ -0,0 +1,190 @@
+use alloc::vec::Vec;
+
+use super::{ActivationFunction, Layout};
+
+/// The bytes every serialized network starts with
+const MAGIC: &[u8; 4] = b"NEAT";
+/// The version of the format that is written
+const VERSION: u8 = 1;
+
+/// A network that calculates the outputs of a genome, without needing `std`
+///
+/// It's created from a genome with `Network::from_genome` and can be stored with [`Network::to_bytes`],
+/// so it can be loaded again with [`Network::from_bytes`] on a device without `std`.
+///
+/// The bytes are little endian and made up of:
+///
+///  - The magic bytes `NEAT` and the version, 1
+///  - The activation function as a byte, 0 for the sigmoid and 1 for the relu
+///  - The number of inputs, calculated nodes and outputs as `u16`s
+///  - For every calculated node, in the order they are calculated, the number of connections as a `u16`,
+///    followed by the index of the value every connection comes from as a `u16` and its weight as an `f32`
+///  - The index of the value of every output as a `u16`
+///
+/// The values are the inputs, the bias and then every calculated node.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Network {
+    pub(super) activation: ActivationFunction,
+    pub(super) layout: Layout<f32>,
+}
+
+impl Network {
+    /// Calculate the outputs of this network
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the number of inputs aren't equal to the number of input nodes.
+    pub fn calculate(&self, inputs: &[f32]) -> Result<Vec<f32>, &'static str> {
+        self.layout.calculate(
+            inputs,
+            1.0,
+            0.0,
+            |sum, weight, value| sum + weight * value,
+            |sum| self.activation.apply(sum),
+        )
+    }
+
+    /// Get the number of inputs of this network
+    pub fn input_size(&self) -> usize {
+        self.layout.input_size
+    }
+
+    /// Get the number of outputs of this network
+    pub fn output_size(&self) -> usize {
+        self.layout.outputs.len()
+    }
+
+    /// Serialize this network into bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if the network has more than `u16::MAX` inputs, nodes or connections to one node
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let as_u16 = |value: usize| u16::try_from(value).expect("Too many values to serialize");
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.push(match self.activation {
+            ActivationFunction::Sigmoid => 0,
+            ActivationFunction::Relu => 1,
+        });
+
+        let layout = &self.layout;
+        for size in [layout.input_size, layout.starts.len(), layout.outputs.len()] {
+            bytes.extend_from_slice(&as_u16(size).to_le_bytes());
+        }
+
+        for node in 0..layout.starts.len() {
+            let connections = layout.connections(node);
+
+            bytes.extend_from_slice(&as_u16(connections.len()).to_le_bytes());
+            for index in connections {
+                bytes.extend_from_slice(&layout.sources[index].to_le_bytes());
+                bytes.extend_from_slice(&layout.weights[index].to_le_bytes());
+            }
+        }
+
+        for output in &layout.outputs {
+            bytes.extend_from_slice(&output.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    /// Deserialize a network from bytes written by [`Network::to_bytes`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the bytes aren't a valid network.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut reader = Reader { bytes };
+
+        if reader.take(4)? != MAGIC {
+            return Err("Not a serialized network");
+        }
+        if reader.take(1)?[0] != VERSION {
+            return Err("Unsupported network version");
+        }
+        let activation = match reader.take(1)?[0] {
+            0 => ActivationFunction::Sigmoid,
+            1 => ActivationFunction::Relu,
+            _ => return Err("Unknown activation function"),
+        };
+
+        let input_size = reader.u16()? as usize;
+        let node_count = reader.u16()? as usize;
+        let output_count = reader.u16()? as usize;
+
+        let mut layout = Layout {
+            input_size,
+            starts: Vec::with_capacity(node_count),
+            sources: Vec::new(),
+            weights: Vec::new(),
+            outputs: Vec::with_capacity(output_count),
+        };
+
+        // Every node can only use the values that are calculated before it
+        let mut value_count = input_size + 1;
+
+        for _ in 0..node_count {
+            layout.starts.push(layout.weights.len());
+
+            for _ in 0..reader.u16()? {
+                let source = reader.u16()?;
+                if source as usize >= value_count {
+                    return Err("Connection from a value that isn't calculated yet");
+                }
+
+                layout.sources.push(source);
+                layout.weights.push(reader.f32()?);
+            }
+
+            value_count += 1;
+        }
+
+        for _ in 0..output_count {
+            let output = reader.u16()?;
+            if output as usize >= value_count {
+                return Err("Output of a value that doesn't exist");
+            }
+
+            layout.outputs.push(output);
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err("Unexpected bytes after the network");
+        }
+
+        Ok(Self { activation, layout })
+    }
+}
+
+/// Reads values from the start of some bytes
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], &'static str> {
+        if self.bytes.len() < length {
+            return Err("Unexpected end of the network");
+        }
+
+        let (taken, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn f32(&mut self) -> Result<f32, &'static str> {
+        Ok(f32::from_le_bytes(
+            self.take(4)?.try_into().expect("Took 4 bytes"),
+        ))
+    }
+
+    fn u16(&mut self) -> Result<u16, &'static str> {
+        Ok(u16::from_le_bytes(
+            self.take(2)?.try_into().expect("Took 2 bytes"),
+        ))
+    }
+}
@@ -6,19 +6,35 @@
 //!
 //! Then I looked on Youtube and found Finn Eggers and his tutorial really helped me with creating this library.
 //!
-//! Doesn't allow recurrent connections in the networks.
+//! Recurrent connections are turned off by default. With `allow_recurrent` in the config they can evolve
+//! and run in a `Ctrnn` or a `SpikingNetwork`, the feed-forward `Calculator` leaves them out.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(clippy::pedantic)]
 #![allow(clippy::must_use_candidate)]
 #![allow(clippy::float_cmp)]
 #![allow(clippy::cast_precision_loss)]
 #![allow(clippy::cast_possible_truncation)]
 #![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_possible_wrap)]
 #![allow(clippy::ptr_arg)]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
 pub mod calculations;
+#[cfg(feature = "std")]
 pub mod data_structures;
+#[cfg(feature = "std")]
 pub mod genome;
+#[cfg(feature = "std")]
+pub mod hyperneat;
+pub mod inference;
+#[cfg(feature = "std")]
 pub mod neat;
+#[cfg(feature = "onnx")]
+pub mod onnx;
+pub mod quantized;
 
+#[cfg(feature = "std")]
 pub use neat::Neat;
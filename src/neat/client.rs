@@ -1,6 +1,10 @@
 use std::{cell::RefCell, fmt::Debug, rc::Rc};
 
-use crate::{calculations::Calculator, genome::Genome, Neat};
+use crate::{
+    calculations::{Calculator, Ctrnn, SpikingNetwork},
+    genome::Genome,
+    Neat,
+};
 
 /// The client that controls the genome and the fitness
 #[derive(Clone)]
@@ -8,8 +12,19 @@ pub struct Client {
     /// The genome of this client
     pub genome: Genome,
     calculator: Option<Calculator>,
+    ctrnn: Option<Ctrnn>,
+    spiking_network: Option<SpikingNetwork>,
     /// The fitness of this client
     pub fitness: f32,
+    /// The objectives of this client, used instead of the fitness when multi-objective evolution is enabled
+    pub objectives: Vec<f32>,
+    /// The behavior of this client, used by novelty search to calculate its novelty
+    pub behavior: Vec<f32>,
+    /// The novelty of this client, calculated in [`Neat::evolve`] when novelty search is enabled
+    pub novelty: f32,
+    score: Option<f32>,
+    /// The number of [ticks](Neat::tick) this client has lived with its current genome, up to `u32::MAX`
+    pub age: u32,
     /// A boolean to determine whether this client is a part of a species or not
     pub has_species: bool,
 }
@@ -22,7 +37,14 @@ impl Client {
         Rc::new(RefCell::new(Self {
             genome,
             calculator: None,
+            ctrnn: None,
+            spiking_network: None,
             fitness: 0.0,
+            objectives: Vec::new(),
+            behavior: Vec::new(),
+            novelty: 0.0,
+            score: None,
+            age: 0,
             has_species: false,
         }))
     }
@@ -31,6 +53,8 @@ impl Client {
     /// Generate a calculator for this genome
     pub fn generate_calculator(&mut self) {
         self.calculator = Some(Calculator::new(self.genome.clone()));
+        self.ctrnn = None;
+        self.spiking_network = None;
     }
 
     /// Calculate the outputs
@@ -60,6 +84,97 @@ impl Client {
             .expect("Failed to calculate")
     }
 
+    /// Advance the genome as a continuous-time recurrent network and return the outputs
+    ///
+    /// The state is kept between calls until [`reset_state`](Client::reset_state) is called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of inputs isn't equal to the number of input nodes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 5);
+    ///
+    /// for mut client in neat.iter_clients() {
+    ///     let mut fitness = 0.0;
+    ///     for step in 0..10 {
+    ///         fitness += client.advance(&vec![step as f32, 1.0], 0.1, 5)[0];
+    ///     }
+    ///
+    ///     client.fitness = fitness;
+    ///     client.reset_state();
+    /// }
+    /// ```
+    pub fn advance(&mut self, inputs: &Vec<f32>, dt: f32, steps: usize) -> Vec<f32> {
+        self.ctrnn
+            .get_or_insert_with(|| Ctrnn::new(&self.genome))
+            .advance(inputs, dt, steps)
+            .expect("Failed to advance")
+    }
+
+    /// Set the weights of the calculator back to the genome's weights, after they changed with plasticity
+    ///
+    /// Call this between episodes, so what was learned in one episode doesn't carry over to the next.
+    pub fn reset_weights(&mut self) {
+        if let Some(calculator) = &mut self.calculator {
+            calculator.reset_weights();
+        }
+    }
+
+    /// Get the genome as a network of spiking neurons, which keeps its state until it's reset
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 5);
+    ///
+    /// for mut client in neat.iter_clients() {
+    ///     let network = client.spiking_network();
+    ///     for _ in 0..200 {
+    ///         network.step(&vec![1.0, 0.5], 0.5).unwrap();
+    ///     }
+    ///
+    ///     client.fitness = client.spiking_network().firing_rates()[0];
+    ///     client.reset_state();
+    /// }
+    /// ```
+    pub fn spiking_network(&mut self) -> &mut SpikingNetwork {
+        self.spiking_network
+            .get_or_insert_with(|| SpikingNetwork::new(&self.genome))
+    }
+
+    /// Reset the state of the continuous-time recurrent network to zero and the spiking neurons to rest
+    pub fn reset_state(&mut self) {
+        if let Some(ctrnn) = &mut self.ctrnn {
+            ctrnn.reset();
+        }
+        if let Some(spiking_network) = &mut self.spiking_network {
+            spiking_network.reset();
+        }
+    }
+
+    /// Get the score that is used in selection
+    ///
+    /// It's the fitness, blended with the novelty when novelty search is enabled,
+    /// or the rank of the objectives in the population with multi-objective evolution.
+    pub fn score(&self) -> f32 {
+        self.score.unwrap_or(self.fitness)
+    }
+
+    #[doc(hidden)]
+    /// Set the score that is used in selection, `None` to use the fitness
+    pub fn set_score(&mut self, score: Option<f32>) {
+        self.score = score;
+    }
+
     #[doc(hidden)]
     /// Calculate the distance from this client's genome to other's genome
     pub fn distance(&self, other: &Client) -> f32 {
@@ -76,8 +191,8 @@ impl Debug for Client {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Client {{ fitness: {:?}, has_species: {:?} }}",
-            self.fitness, self.has_species
+            "Client {{ fitness: {:?}, novelty: {:?}, has_species: {:?} }}",
+            self.fitness, self.novelty, self.has_species
         )
     }
 }
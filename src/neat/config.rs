@@ -1,26 +1,9 @@
 use once_cell::sync::OnceCell;
 use std::fs;
 
-pub static CONFIG: OnceCell<Config> = OnceCell::new();
+pub use crate::inference::ActivationFunction;
 
-#[derive(Debug)]
-/// The activation function enum to determine what activation function you're using
-pub enum ActivationFunction {
-    /// The sigmoid activation function
-    Sigmoid,
-    /// The ReLu activation function
-    Relu,
-}
-
-impl From<&str> for ActivationFunction {
-    fn from(value: &str) -> Self {
-        match value {
-            "relu" => ActivationFunction::Relu,
-            "sigmoid" => ActivationFunction::Sigmoid,
-            _ => panic!("Wrong activation input"),
-        }
-    }
-}
+pub static CONFIG: OnceCell<Config> = OnceCell::new();
 
 #[derive(Debug)]
 /// The struct that stores all the config options
@@ -47,6 +30,14 @@ pub struct Config {
     pub prob_mutate_weight_random: f32,
     /// The probability of mutating and toggling a link
     pub prob_mutate_toggle_link: f32,
+    /// The probability of mutating and shifting the time constant of a node
+    pub prob_mutate_time_constant: f32,
+    /// The probability of mutating and shifting the bias of a node
+    pub prob_mutate_bias: f32,
+    /// The probability of mutating and shifting one of the Izhikevich parameters of a node
+    pub prob_mutate_izhikevich: f32,
+    /// The probability of mutating and shifting one of the Hebbian coefficients of a link
+    pub prob_mutate_hebbian: f32,
 
     /// The threshold for creating a new species
     pub species_threshold: f32,
@@ -56,6 +47,45 @@ pub struct Config {
 
     /// The activation function to use
     pub activation: ActivationFunction,
+
+    /// How simpler genomes are preferred in the species
+    pub parsimony: Parsimony,
+    /// The penalty for every enabled connection
+    pub parsimony_connection_penalty: f32,
+    /// The penalty for every hidden node
+    pub parsimony_node_penalty: f32,
+
+    /// Whether calculators change their weights with the [Hebbian](crate::genome::Hebbian) coefficients of the links
+    pub plasticity: bool,
+
+    /// Whether mutations may add connections that form cycles, for the [`Ctrnn`](crate::calculations::Ctrnn)
+    /// and the [`SpikingNetwork`](crate::calculations::SpikingNetwork)
+    pub allow_recurrent: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+/// How the complexity of a genome is penalized when its fitness is adjusted in a species
+///
+/// The complexity is the number of enabled connections and hidden nodes, multiplied by their penalties.
+pub enum Parsimony {
+    /// Don't penalize the complexity
+    #[default]
+    None,
+    /// Subtract the complexity from the fitness
+    Linear,
+    /// Only use the complexity to break ties between equal fitnesses
+    Lexicographic,
+}
+
+impl From<&str> for Parsimony {
+    fn from(value: &str) -> Self {
+        match value {
+            "none" => Parsimony::None,
+            "linear" => Parsimony::Linear,
+            "lexicographic" => Parsimony::Lexicographic,
+            _ => panic!("Wrong parsimony input"),
+        }
+    }
 }
 
 impl Config {
@@ -80,12 +110,24 @@ impl Config {
             prob_mutate_weight_shift: variables[7],
             prob_mutate_weight_random: variables[8],
             prob_mutate_toggle_link: variables[9],
+            prob_mutate_time_constant: 0.0,
+            prob_mutate_bias: 0.0,
+            prob_mutate_izhikevich: 0.0,
+            prob_mutate_hebbian: 0.0,
 
             species_threshold: variables[10],
 
             kill_percentage: variables[11],
 
             activation: activation.into(),
+
+            parsimony: Parsimony::None,
+            parsimony_connection_penalty: 0.0,
+            parsimony_node_penalty: 0.0,
+
+            plasticity: false,
+
+            allow_recurrent: false,
         }
     }
 
@@ -94,6 +136,7 @@ impl Config {
     /// # Panics
     ///
     /// Panics if it finds an unrecongized pattern in the config file
+    #[allow(clippy::too_many_lines)]
     pub fn from_file(filename: &str) -> Self {
         let mut config = Config::init_zero();
 
@@ -176,6 +219,34 @@ impl Config {
                             .parse()
                             .expect("No valid float supplied");
                     }
+                    "prob_mutate_time_constant" => {
+                        config.prob_mutate_time_constant = split
+                            .next()
+                            .expect("No number after parameter")
+                            .parse()
+                            .expect("No valid float supplied");
+                    }
+                    "prob_mutate_bias" => {
+                        config.prob_mutate_bias = split
+                            .next()
+                            .expect("No number after parameter")
+                            .parse()
+                            .expect("No valid float supplied");
+                    }
+                    "prob_mutate_izhikevich" => {
+                        config.prob_mutate_izhikevich = split
+                            .next()
+                            .expect("No number after parameter")
+                            .parse()
+                            .expect("No valid float supplied");
+                    }
+                    "prob_mutate_hebbian" => {
+                        config.prob_mutate_hebbian = split
+                            .next()
+                            .expect("No number after parameter")
+                            .parse()
+                            .expect("No valid float supplied");
+                    }
 
                     "species_threshold" => {
                         config.species_threshold = split
@@ -194,6 +265,34 @@ impl Config {
                     "activation" => {
                         config.activation = split.next().expect("No string after parameter").into();
                     }
+
+                    "parsimony" => {
+                        config.parsimony = split.next().expect("No string after parameter").into();
+                    }
+                    "parsimony_connection_penalty" => {
+                        config.parsimony_connection_penalty = split
+                            .next()
+                            .expect("No number after parameter")
+                            .parse()
+                            .expect("No valid float supplied");
+                    }
+                    "parsimony_node_penalty" => {
+                        config.parsimony_node_penalty = split
+                            .next()
+                            .expect("No number after parameter")
+                            .parse()
+                            .expect("No valid float supplied");
+                    }
+
+                    "plasticity" => {
+                        config.plasticity =
+                            split.next().expect("No bool after parameter") == "true";
+                    }
+
+                    "allow_recurrent" => {
+                        config.allow_recurrent =
+                            split.next().expect("No bool after parameter") == "true";
+                    }
                     "" => (),
                     _ => panic!("No recognized pattern"),
                 }
@@ -218,12 +317,24 @@ impl Config {
             prob_mutate_weight_shift: 0.0,
             prob_mutate_weight_random: 0.0,
             prob_mutate_toggle_link: 0.0,
+            prob_mutate_time_constant: 0.0,
+            prob_mutate_bias: 0.0,
+            prob_mutate_izhikevich: 0.0,
+            prob_mutate_hebbian: 0.0,
 
             species_threshold: 0.0,
 
             kill_percentage: 0.0,
 
             activation: ActivationFunction::Relu,
+
+            parsimony: Parsimony::None,
+            parsimony_connection_penalty: 0.0,
+            parsimony_node_penalty: 0.0,
+
+            plasticity: false,
+
+            allow_recurrent: false,
         }
     }
 
@@ -235,7 +346,7 @@ impl Config {
 
 #[cfg(test)]
 mod tests {
-    use super::Config;
+    use super::*;
 
     #[test]
     fn from_file() {
@@ -257,5 +368,26 @@ mod tests {
         assert_eq!(Config::global().species_threshold, 4.0);
 
         assert_eq!(Config::global().kill_percentage, 0.2);
+
+        assert_eq!(Config::global().parsimony, Parsimony::None);
+        assert!(!Config::global().plasticity);
+        assert!(!Config::global().allow_recurrent);
+    }
+
+    #[test]
+    fn parsimony() {
+        let path = std::env::temp_dir().join("profqu_neat_parsimony_config.txt");
+        fs::write(
+            &path,
+            "parsimony: lexicographic\nparsimony_connection_penalty: 0.5\nparsimony_node_penalty: 2.0",
+        )
+        .expect("Failed to write config");
+
+        let config = Config::from_file(path.to_str().expect("Invalid path"));
+        fs::remove_file(path).expect("Failed to remove config");
+
+        assert_eq!(config.parsimony, Parsimony::Lexicographic);
+        assert_eq!(config.parsimony_connection_penalty, 0.5);
+        assert_eq!(config.parsimony_node_penalty, 2.0);
     }
 }
@@ -0,0 +1,13 @@
+/// The topology every genome starts out with when the population is reset
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum InitialTopology {
+    /// No connections at all
+    #[default]
+    Unconnected,
+    /// Every input and the bias are connected to every output
+    FullyConnected,
+    /// Every input and the bias are connected to every output with some probability
+    PartiallyConnected(f32),
+    /// Every input and the bias are connected to a number of hidden nodes, which are all connected to every output
+    FullyConnectedHidden(u32),
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{genome::Genome, Neat};
+
+/// The best genome that was found for a cell of a [`MapElites`] archive
+#[derive(Clone, Debug)]
+pub struct Elite {
+    /// The genome of this elite
+    pub genome: Genome,
+    /// The fitness of the genome
+    pub fitness: f32,
+    /// The behavior of the genome, which decided its cell
+    pub behavior: Vec<f32>,
+}
+
+/// A quality-diversity archive that keeps the best genome for every cell of a behavior grid
+///
+/// New genomes are bred from the elites with the innovation numbers of a [`Neat`],
+/// evaluated by the user and then inserted back into the archive.
+///
+/// # Examples
+///
+/// ```rust
+/// use profqu_neat::Neat;
+/// use profqu_neat::neat::MapElites;
+///
+/// Neat::test_config();
+/// let mut neat = Neat::new(2, 1, 1);
+///
+/// // A 10 by 10 grid over the two outputs of the behavior
+/// let mut map_elites = MapElites::new(vec![10, 10], vec![(0.0, 1.0), (0.0, 1.0)]);
+///
+/// for _ in 0..100 {
+///     let genome = map_elites.generate(&mut neat);
+///
+///     let mut calculator = profqu_neat::calculations::Calculator::new(genome.clone());
+///     let low = calculator.calculate(&vec![0.0, 1.0]).unwrap()[0];
+///     let high = calculator.calculate(&vec![1.0, 0.0]).unwrap()[0];
+///
+///     map_elites.insert(genome, high - low, vec![low, high]);
+/// }
+///
+/// assert!(map_elites.coverage() > 0.0);
+/// println!("QD-score: {}", map_elites.qd_score());
+/// ```
+#[derive(Clone, Debug)]
+pub struct MapElites {
+    /// The probability that a new genome is bred from two elites instead of mutating one
+    pub crossover_probability: f32,
+    resolution: Vec<usize>,
+    bounds: Vec<(f32, f32)>,
+    cells: HashMap<usize, Elite>,
+}
+
+impl MapElites {
+    /// Create an empty archive with a number of cells and the bounds of the behavior for every dimension
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resolution and the bounds don't have the same number of dimensions,
+    /// if a dimension has no cells or if the maximum of a bound isn't above its minimum
+    pub fn new(resolution: Vec<usize>, bounds: Vec<(f32, f32)>) -> Self {
+        assert_eq!(resolution.len(), bounds.len());
+        assert!(resolution.iter().all(|cells| *cells > 0));
+        assert!(bounds.iter().all(|(min, max)| max > min));
+
+        Self {
+            crossover_probability: 0.5,
+            resolution,
+            bounds,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Breed a new genome from random elites, or an empty genome if the archive is empty
+    pub fn generate(&self, neat: &mut Neat) -> Genome {
+        let elites: Vec<&Elite> = self.cells.values().collect();
+        let mut rng = rand::thread_rng();
+
+        let Some(parent) = elites.choose(&mut rng) else {
+            let mut genome = neat.empty_genome();
+            genome.mutate(neat);
+            return genome;
+        };
+
+        let mut genome = if rng.gen::<f32>() < self.crossover_probability {
+            let other = elites[rng.gen_range(0..elites.len())];
+
+            if parent.fitness > other.fitness {
+                Genome::crossover(neat, &parent.genome, &other.genome)
+            } else {
+                Genome::crossover(neat, &other.genome, &parent.genome)
+            }
+        } else {
+            parent.genome.clone()
+        };
+
+        genome.mutate(neat);
+        genome
+    }
+
+    /// Insert a genome in the cell of its behavior if the cell is empty or the genome is fitter,
+    /// returns whether it was inserted
+    ///
+    /// # Panics
+    ///
+    /// Panics if the behavior doesn't have the same number of dimensions as the archive
+    pub fn insert(&mut self, genome: Genome, fitness: f32, behavior: Vec<f32>) -> bool {
+        let cell = self.cell(&behavior);
+
+        if let Some(elite) = self.cells.get(&cell) {
+            if elite.fitness >= fitness {
+                return false;
+            }
+        }
+
+        self.cells.insert(
+            cell,
+            Elite {
+                genome,
+                fitness,
+                behavior,
+            },
+        );
+        true
+    }
+
+    /// Get the elite in the cell of a behavior
+    ///
+    /// # Panics
+    ///
+    /// Panics if the behavior doesn't have the same number of dimensions as the archive
+    pub fn get(&self, behavior: &[f32]) -> Option<&Elite> {
+        self.cells.get(&self.cell(behavior))
+    }
+
+    /// Iterate over all the elites in the archive
+    pub fn elites(&self) -> impl Iterator<Item = &Elite> {
+        self.cells.values()
+    }
+
+    /// The number of cells that have an elite
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Return if there are no elites yet
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The fraction of the cells that have an elite
+    pub fn coverage(&self) -> f32 {
+        self.cells.len() as f32 / self.resolution.iter().product::<usize>() as f32
+    }
+
+    /// The sum of the fitness of all elites
+    pub fn qd_score(&self) -> f32 {
+        self.cells.values().map(|elite| elite.fitness).sum()
+    }
+
+    /// The index of the cell a behavior falls in, behaviors outside of the bounds are put in the closest cell
+    fn cell(&self, behavior: &[f32]) -> usize {
+        assert_eq!(behavior.len(), self.resolution.len());
+
+        let mut cell = 0;
+        for ((value, (min, max)), resolution) in
+            behavior.iter().zip(&self.bounds).zip(&self.resolution)
+        {
+            let position = ((value - min) / (max - min) * *resolution as f32) as usize;
+            cell = cell * resolution + position.min(resolution - 1);
+        }
+
+        cell
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert() {
+        Neat::test_config();
+        let mut neat = Neat::new(2, 1, 1);
+        let mut map_elites = MapElites::new(vec![2, 4], vec![(0.0, 1.0), (-1.0, 1.0)]);
+
+        assert!(map_elites.is_empty());
+        assert!(map_elites.insert(neat.empty_genome(), 1.0, vec![0.2, 0.1]));
+        assert!(map_elites.insert(neat.empty_genome(), 2.0, vec![0.7, 0.1]));
+
+        // The same cell, but not fitter
+        assert!(!map_elites.insert(neat.empty_genome(), 1.0, vec![0.4, 0.4]));
+        assert!(map_elites.insert(neat.empty_genome(), 3.0, vec![0.4, 0.4]));
+
+        // Outside of the bounds
+        assert!(map_elites.insert(neat.empty_genome(), 1.0, vec![-5.0, 5.0]));
+
+        assert_eq!(map_elites.len(), 3);
+        assert_eq!(map_elites.coverage(), 3.0 / 8.0);
+        assert_eq!(map_elites.qd_score(), 6.0);
+        assert_eq!(
+            map_elites.get(&[0.0, 0.0]).map(|elite| elite.fitness),
+            Some(3.0)
+        );
+        assert_eq!(
+            map_elites
+                .get(&[0.0, 1.0])
+                .map(|elite| elite.behavior.clone()),
+            Some(vec![-5.0, 5.0])
+        );
+        assert!(map_elites.get(&[1.0, -1.0]).is_none());
+    }
+
+    #[test]
+    fn generate() {
+        Neat::test_config();
+        let mut neat = Neat::new(2, 1, 1);
+        let mut map_elites = MapElites::new(vec![5], vec![(0.0, 1.0)]);
+
+        let genome = map_elites.generate(&mut neat);
+        assert!(genome.nodes.len() >= 4);
+
+        let mut parent = neat.empty_genome();
+        parent.add_connection(&mut neat, 0, 3);
+        map_elites.insert(parent, 1.0, vec![0.5]);
+
+        // Every child keeps the connection of the only elite
+        for _ in 0..10 {
+            let child = map_elites.generate(&mut neat);
+            assert!(!child.connections.is_empty());
+        }
+    }
+}
@@ -2,11 +2,24 @@
 
 mod client;
 mod config;
+mod initial_topology;
+mod map_elites;
 #[allow(clippy::module_inception)]
 mod neat;
+mod novelty_search;
+mod pareto;
+mod phased_search;
+mod real_time;
 mod species;
+mod statistics;
 
 pub use client::Client;
-pub use config::{ActivationFunction, Config};
+pub use config::{ActivationFunction, Config, Parsimony};
+pub use initial_topology::InitialTopology;
+pub use map_elites::{Elite, MapElites};
 pub use neat::{Neat, MAX_NODES};
+pub use novelty_search::NoveltySearch;
+pub use phased_search::{Phase, PhasedSearch};
+pub use real_time::RealTime;
 pub use species::Species;
+pub use statistics::Statistics;
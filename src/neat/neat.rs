@@ -1,13 +1,16 @@
-use rand::seq::SliceRandom;
+use rand::{seq::SliceRandom, thread_rng, Rng};
 use std::{
     cell::{RefCell, RefMut},
     collections::HashMap,
     rc::Rc,
 };
 
-use crate::genome::{ConnectionGene, Genome, NodeGene};
+use crate::genome::{ConnectionGene, Genome, NodeGene, NodeType};
 
-use super::{config::CONFIG, Client, Config, Species};
+use super::{
+    config::CONFIG, pareto, Client, Config, InitialTopology, NoveltySearch, Phase, PhasedSearch,
+    RealTime, Species, Statistics,
+};
 
 #[cfg(test)]
 #[path = "neat_test.rs"]
@@ -46,12 +49,22 @@ pub const MAX_NODES: u64 = 2u64.pow(20);
 /// ```
 pub struct Neat {
     all_connections: HashMap<u64, ConnectionGene>,
+    connection_innovations: u32,
     all_nodes: Vec<NodeGene>,
     clients: Vec<Rc<RefCell<Client>>>,
     species: Vec<Species>,
+    input_nodes: Vec<u32>,
+    bias_node: u32,
+    output_nodes: Vec<u32>,
     input_size: u32,
     output_size: u32,
     population_size: u32,
+    initial_topology: InitialTopology,
+    phased_search: Option<PhasedSearch>,
+    novelty_search: Option<NoveltySearch>,
+    multi_objective: bool,
+    real_time: Option<RealTime>,
+    statistics: Statistics,
 }
 
 impl Neat {
@@ -65,12 +78,22 @@ impl Neat {
     pub fn new(input_size: u32, output_size: u32, population_size: u32) -> Self {
         let mut neat = Self {
             all_connections: HashMap::new(),
+            connection_innovations: 0,
             all_nodes: Vec::new(),
             clients: Vec::new(),
             species: Vec::new(),
+            input_nodes: Vec::new(),
+            bias_node: 0,
+            output_nodes: Vec::new(),
             input_size,
             output_size,
             population_size,
+            initial_topology: InitialTopology::Unconnected,
+            phased_search: None,
+            novelty_search: None,
+            multi_objective: false,
+            real_time: None,
+            statistics: Statistics::default(),
         };
 
         neat.reset(input_size, output_size, population_size);
@@ -98,29 +121,318 @@ impl Neat {
         self.population_size = population_size;
 
         self.all_connections.clear();
+        self.connection_innovations = 0;
         self.all_nodes.clear();
+        self.input_nodes.clear();
+        self.output_nodes.clear();
         self.clients.clear();
 
+        self.statistics = Statistics::default();
+        if let Some(phased_search) = &mut self.phased_search {
+            let deletion_multiplier = phased_search.deletion_multiplier;
+            *phased_search = PhasedSearch::new(
+                phased_search.complexity_margin,
+                phased_search.stagnation_limit,
+            );
+            phased_search.deletion_multiplier = deletion_multiplier;
+        }
+        if let Some(novelty_search) = &mut self.novelty_search {
+            *novelty_search = NoveltySearch::new(
+                novelty_search.k,
+                novelty_search.archive_threshold,
+                novelty_search.fitness_weight,
+            );
+        }
+        if let Some(real_time) = &mut self.real_time {
+            *real_time = RealTime::new(real_time.interval, real_time.minimum_age);
+        }
+
         for input_index in 0..input_size as usize {
             let y = (input_index + 1) as f32 / (input_size + 1) as f32;
-            self.create_node(0.1, y);
+            let node = self.create_typed_node(NodeType::Input, 0.1, y);
+            self.input_nodes.push(node.innovation_number);
         }
 
         // Create a bias node
-        self.create_node(0.1, 0.9);
+        self.bias_node = self
+            .create_typed_node(NodeType::Bias, 0.1, 0.9)
+            .innovation_number;
 
         for output_index in 0..output_size as usize {
             let y = (output_index + 1) as f32 / (output_size + 1) as f32;
-            self.create_node(0.9, y);
+            let node = self.create_typed_node(NodeType::Output, 0.9, y);
+            self.output_nodes.push(node.innovation_number);
+        }
+
+        if let InitialTopology::FullyConnectedHidden(hidden_size) = self.initial_topology {
+            for hidden_index in 0..hidden_size as usize {
+                let y = (hidden_index + 1) as f32 / (hidden_size + 1) as f32;
+                self.create_node(0.5, y);
+            }
         }
 
         for _client_index in 0..population_size as usize {
-            let client = Client::new(self.empty_genome());
+            let client = Client::new(self.initial_genome());
             client.borrow_mut().generate_calculator();
             self.clients.push(client);
         }
     }
 
+    /// Create a new neat struct with a population of mutated copies of `genome`
+    ///
+    /// The nodes and connections of `genome` are registered with their innovation numbers,
+    /// so the population can keep evolving it. The first client is an exact copy.
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(3, 2, 15);
+    ///
+    /// let mut genome = neat.empty_genome();
+    /// genome.add_connection(&mut neat, 0, 4);
+    /// genome.mutate_node(&mut neat);
+    ///
+    /// let neat = Neat::from_seed_genome(&genome, 10);
+    /// assert_eq!(neat.get_client(0).borrow().genome, genome);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `genome` doesn't have a bias node
+    pub fn from_seed_genome(genome: &Genome, population_size: u32) -> Self {
+        let nodes_of_type = |node_type: NodeType| -> Vec<u32> {
+            genome
+                .nodes
+                .data
+                .iter()
+                .filter(|node| node.node_type == node_type)
+                .map(|node| node.innovation_number)
+                .collect()
+        };
+
+        let input_nodes = nodes_of_type(NodeType::Input);
+        let bias_node = *nodes_of_type(NodeType::Bias)
+            .first()
+            .expect("Genome has no bias node");
+        let output_nodes = nodes_of_type(NodeType::Output);
+
+        let mut neat = Self {
+            all_connections: HashMap::new(),
+            connection_innovations: 0,
+            all_nodes: Vec::new(),
+            clients: Vec::new(),
+            species: Vec::new(),
+            input_size: input_nodes.len() as u32,
+            output_size: output_nodes.len() as u32,
+            input_nodes,
+            bias_node,
+            output_nodes,
+            population_size,
+            initial_topology: InitialTopology::Unconnected,
+            phased_search: None,
+            novelty_search: None,
+            multi_objective: false,
+            real_time: None,
+            statistics: Statistics::default(),
+        };
+
+        // Fill the gaps left by nodes that aren't in this genome
+        let highest_node = genome
+            .nodes
+            .data
+            .iter()
+            .map(|node| node.innovation_number)
+            .max()
+            .unwrap_or(0);
+
+        for innovation_number in 1..=highest_node {
+            let mut node = NodeGene::new(innovation_number);
+            node.x = 0.5;
+            node.y = 0.5;
+            neat.all_nodes.push(node);
+        }
+
+        for node in &genome.nodes.data {
+            neat.all_nodes[node.innovation_number as usize - 1] = *node;
+        }
+
+        for connection in &genome.connections.data {
+            let mut connection = *connection;
+
+            let replaced_by = NodeGene::new(connection.replace_index as u32);
+            if !genome.nodes.contains(&replaced_by) {
+                connection.replace_index = 0;
+            }
+
+            neat.all_connections
+                .insert(connection.hash_code(), connection);
+            neat.connection_innovations = neat
+                .connection_innovations
+                .max(connection.innovation_number);
+        }
+
+        for client_index in 0..population_size {
+            let mut seed = genome.clone();
+            if client_index > 0 {
+                seed.mutate(&mut neat);
+            }
+
+            let client = Client::new(seed);
+            client.borrow_mut().generate_calculator();
+            neat.clients.push(client);
+        }
+
+        neat
+    }
+
+    /// Add new input nodes to the genome of every client, keeping all evolved structure
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(3, 2, 15);
+    ///
+    /// neat.add_inputs(2);
+    ///
+    /// let client = neat.get_client(0);
+    /// assert_eq!(client.borrow_mut().calculate(&vec![0.0; 5]).len(), 2);
+    /// ```
+    pub fn add_inputs(&mut self, amount: u32) {
+        for _ in 0..amount {
+            let node = self.create_typed_node(NodeType::Input, 0.1, 0.0);
+            self.input_nodes.push(node.innovation_number);
+        }
+
+        self.input_size += amount;
+        self.update_input_output_nodes();
+    }
+
+    /// Add new output nodes to the genome of every client, keeping all evolved structure
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(3, 2, 15);
+    ///
+    /// neat.add_outputs(2);
+    ///
+    /// let client = neat.get_client(0);
+    /// assert_eq!(client.borrow_mut().calculate(&vec![0.0; 3]).len(), 4);
+    /// ```
+    pub fn add_outputs(&mut self, amount: u32) {
+        for _ in 0..amount {
+            let node = self.create_typed_node(NodeType::Output, 0.9, 0.0);
+            self.output_nodes.push(node.innovation_number);
+        }
+
+        self.output_size += amount;
+        self.update_input_output_nodes();
+    }
+
+    /// Remove the input node at `index` and its connections from the genome of every client
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds
+    pub fn remove_input(&mut self, index: usize) {
+        let innovation_number = self.input_nodes.remove(index);
+
+        self.input_size -= 1;
+        self.remove_node(innovation_number);
+        self.update_input_output_nodes();
+    }
+
+    /// Remove the output node at `index` and its connections from the genome of every client
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds
+    pub fn remove_output(&mut self, index: usize) {
+        let innovation_number = self.output_nodes.remove(index);
+
+        self.output_size -= 1;
+        self.remove_node(innovation_number);
+        self.update_input_output_nodes();
+    }
+
+    #[doc(hidden)]
+    /// Remove a node and all of its connections from every client's genome
+    fn remove_node(&mut self, innovation_number: u32) {
+        let node = NodeGene::new(innovation_number);
+
+        for client in &self.clients {
+            let genome = &mut client.borrow_mut().genome;
+
+            for connection in genome.connections.data.clone() {
+                if connection.from.innovation_number == innovation_number
+                    || connection.to.innovation_number == innovation_number
+                {
+                    genome.connections.remove_value(&connection);
+                }
+            }
+
+            genome.nodes.remove_value(&node);
+        }
+    }
+
+    #[doc(hidden)]
+    /// Spread out the input and output nodes again and put them in the right order in every client's genome
+    fn update_input_output_nodes(&mut self) {
+        for (nodes, x) in [
+            (self.input_nodes.clone(), 0.1),
+            (self.output_nodes.clone(), 0.9),
+        ] {
+            for (index, innovation_number) in nodes.iter().enumerate() {
+                let node = &mut self.all_nodes[*innovation_number as usize - 1];
+                node.x = x;
+                node.y = (index + 1) as f32 / (nodes.len() + 1) as f32;
+            }
+        }
+
+        let empty_genome = self.empty_genome();
+
+        for client in &self.clients {
+            let mut client = client.borrow_mut();
+
+            for node in &empty_genome.nodes.data {
+                client.genome.update_node(*node);
+            }
+
+            // The calculator expects the inputs, bias and outputs to come first
+            let mut nodes = empty_genome.nodes.clone();
+            for node in &client.genome.nodes.data {
+                if node.node_type == NodeType::Hidden {
+                    nodes.add(*node);
+                }
+            }
+
+            client.genome.nodes = nodes;
+            client.generate_calculator();
+        }
+    }
+
+    /// Set the topology every genome starts out with and reset the population with it
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    /// use profqu_neat::neat::InitialTopology;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(3, 2, 15);
+    ///
+    /// neat.set_initial_topology(InitialTopology::FullyConnected);
+    ///
+    /// let client = neat.get_client(0);
+    /// assert_eq!(client.borrow().genome.connections.len(), 8);
+    /// ```
+    pub fn set_initial_topology(&mut self, initial_topology: InitialTopology) {
+        self.initial_topology = initial_topology;
+        self.reset(self.input_size, self.output_size, self.population_size);
+    }
+
     #[doc(hidden)]
     /// Load the config at "src/test_config.txt"
     pub fn test_config() -> bool {
@@ -151,20 +463,97 @@ impl Neat {
     pub fn empty_genome(&mut self) -> Genome {
         let mut genome = Genome::new();
 
-        for index in 0..self.input_size as usize + 1 + self.output_size as usize {
-            genome
-                .nodes
-                .add(self.get_node(index + 1).expect("Failed to get a node"));
+        let mut innovation_numbers = self.input_nodes.clone();
+        innovation_numbers.push(self.bias_node);
+        innovation_numbers.extend(&self.output_nodes);
+
+        for innovation_number in innovation_numbers {
+            genome.nodes.add(
+                self.get_node(innovation_number as usize)
+                    .expect("Failed to get a node"),
+            );
+        }
+
+        genome
+    }
+
+    #[doc(hidden)]
+    /// Create a genome with the initial topology and random weights
+    pub fn initial_genome(&mut self) -> Genome {
+        let mut genome = self.empty_genome();
+
+        // The inputs include the bias node
+        let inputs = 0..self.input_size as usize + 1;
+        let outputs = inputs.end..inputs.end + self.output_size as usize;
+
+        match self.initial_topology {
+            InitialTopology::Unconnected => (),
+            InitialTopology::FullyConnected => {
+                for input in inputs {
+                    for output in outputs.clone() {
+                        self.add_initial_connection(&mut genome, input, output);
+                    }
+                }
+            }
+            InitialTopology::PartiallyConnected(probability) => {
+                for input in inputs {
+                    for output in outputs.clone() {
+                        if probability > rand::random() {
+                            self.add_initial_connection(&mut genome, input, output);
+                        }
+                    }
+                }
+            }
+            InitialTopology::FullyConnectedHidden(hidden_size) => {
+                let hidden = outputs.end..outputs.end + hidden_size as usize;
+
+                for index in hidden.clone() {
+                    genome.nodes.add(
+                        self.get_node(index + 1)
+                            .expect("Failed to get a hidden node"),
+                    );
+                }
+
+                for hidden_index in hidden {
+                    for input in inputs.clone() {
+                        self.add_initial_connection(&mut genome, input, hidden_index);
+                    }
+                    for output in outputs.clone() {
+                        self.add_initial_connection(&mut genome, hidden_index, output);
+                    }
+                }
+            }
         }
 
         genome
     }
 
     #[doc(hidden)]
-    /// Create a new node with certain x and y coordinates
+    /// Add a connection with a random weight between the nodes at 'from' and 'to' in 'genome'
+    fn add_initial_connection(&mut self, genome: &mut Genome, from: usize, to: usize) {
+        let strength = Config::global().weight_random_strength;
+
+        let mut connection = self.get_connection(
+            *genome.nodes.get(from).expect("Failed to find 'from' node"),
+            *genome.nodes.get(to).expect("Failed to find 'to' node"),
+        );
+        connection.weight = thread_rng().gen_range(-strength..=strength);
+
+        genome.connections.add(connection);
+    }
+
+    #[doc(hidden)]
+    /// Create a new hidden node with certain x and y coordinates
     pub fn create_node(&mut self, x: f32, y: f32) -> NodeGene {
+        self.create_typed_node(NodeType::Hidden, x, y)
+    }
+
+    #[doc(hidden)]
+    /// Create a new node of some type with certain x and y coordinates
+    pub fn create_typed_node(&mut self, node_type: NodeType, x: f32, y: f32) -> NodeGene {
         let mut node = NodeGene::new(self.all_nodes.len() as u32 + 1);
 
+        node.node_type = node_type;
         node.x = x;
         node.y = y;
 
@@ -193,7 +582,8 @@ impl Neat {
         if let Some(found) = self.all_connections.get(&connection_gene.hash_code()) {
             connection_gene.innovation_number = found.innovation_number;
         } else {
-            connection_gene.innovation_number = self.all_connections.len() as u32 + 1;
+            self.connection_innovations += 1;
+            connection_gene.innovation_number = self.connection_innovations;
             self.all_connections
                 .insert(connection_gene.hash_code(), connection_gene);
         }
@@ -220,10 +610,155 @@ impl Neat {
         }
     }
 
+    /// Enable or disable phased search, which alternates between complexifying and simplifying phases
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    /// use profqu_neat::neat::{Phase, PhasedSearch};
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(3, 3, 15);
+    ///
+    /// neat.set_phased_search(Some(PhasedSearch::new(20.0, 5)));
+    /// assert_eq!(neat.phase(), Phase::Complexifying);
+    /// ```
+    pub fn set_phased_search(&mut self, phased_search: Option<PhasedSearch>) {
+        self.phased_search = phased_search;
+    }
+
+    /// Get the phased search, if it's enabled
+    pub fn phased_search(&self) -> Option<&PhasedSearch> {
+        self.phased_search.as_ref()
+    }
+
+    /// Get the phase the search is currently in, always complexifying if phased search is disabled
+    pub fn phase(&self) -> Phase {
+        match self.phased_search {
+            Some(phased_search) => phased_search.phase(),
+            None => Phase::Complexifying,
+        }
+    }
+
+    /// Enable or disable novelty search, which selects on the novelty of the clients' behaviors
+    ///
+    /// Set the [behavior](Client::behavior) of every client before calling [`evolve`](Neat::evolve).
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    /// use profqu_neat::neat::NoveltySearch;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 15);
+    ///
+    /// neat.set_novelty_search(Some(NoveltySearch::new(5, 0.5, 0.0)));
+    ///
+    /// for mut client in neat.iter_clients() {
+    ///     let outputs = client.calculate(&vec![1.0, 0.0]);
+    ///     client.fitness = outputs[0];
+    ///     client.behavior = outputs;
+    /// }
+    /// neat.evolve();
+    /// ```
+    pub fn set_novelty_search(&mut self, novelty_search: Option<NoveltySearch>) {
+        self.novelty_search = novelty_search;
+    }
+
+    /// Get the novelty search with its archive, if it's enabled
+    pub fn novelty_search(&self) -> Option<&NoveltySearch> {
+        self.novelty_search.as_ref()
+    }
+
+    /// Enable or disable multi-objective evolution, which selects on the [objectives](Client::objectives)
+    /// instead of the fitness
+    ///
+    /// The whole population is ranked by Pareto front and crowding distance, like in NSGA-II.
+    /// All objectives are maximized and the ranking takes precedence over novelty search.
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 15);
+    ///
+    /// neat.set_multi_objective(true);
+    ///
+    /// for mut client in neat.iter_clients() {
+    ///     let accuracy = client.calculate(&vec![1.0, 0.0])[0];
+    ///     let size = client.genome.connections.len() as f32;
+    ///     client.objectives = vec![accuracy, -size];
+    /// }
+    /// neat.evolve();
+    ///
+    /// assert!(!neat.pareto_front().is_empty());
+    /// ```
+    pub fn set_multi_objective(&mut self, multi_objective: bool) {
+        self.multi_objective = multi_objective;
+    }
+
+    /// Enable or disable real-time evolution, where clients are replaced one at a time with [`tick`](Neat::tick)
+    /// instead of all at once with [`evolve`](Neat::evolve)
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    /// use profqu_neat::neat::RealTime;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 15);
+    ///
+    /// // Replace a client every 10 ticks, once it has lived for at least 50 ticks
+    /// neat.set_real_time(Some(RealTime::new(10, 50)));
+    ///
+    /// for _ in 0..200 {
+    ///     for mut client in neat.iter_clients() {
+    ///         let output = client.calculate(&vec![1.0, 0.0])[0];
+    ///         client.fitness += output;
+    ///     }
+    ///
+    ///     if let Some(client) = neat.tick() {
+    ///         println!("A new client was born: {:?}", client.borrow());
+    ///     }
+    /// }
+    /// ```
+    pub fn set_real_time(&mut self, real_time: Option<RealTime>) {
+        self.real_time = real_time;
+    }
+
+    /// Get the settings of real-time evolution, if it's enabled
+    pub fn real_time(&self) -> Option<&RealTime> {
+        self.real_time.as_ref()
+    }
+
+    /// Set the number of clients, the population grows or shrinks to this size on the next [`evolve`](Neat::evolve)
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(3, 3, 15);
+    ///
+    /// neat.set_population_size(30);
+    /// neat.set_fitness(&vec![1.0; 15]);
+    /// neat.evolve();
+    ///
+    /// assert_eq!(neat.iter_clients().len(), 30);
+    /// ```
+    pub fn set_population_size(&mut self, population_size: u32) {
+        self.population_size = population_size;
+    }
+
+    /// Get the statistics of the last generation
+    pub fn statistics(&self) -> Statistics {
+        self.statistics
+    }
+
     /// A wrapper function for all the evolution steps
     pub fn evolve(&mut self) {
+        self.update_novelty();
         self.gen_species();
+        self.rank_objectives();
+        self.update_statistics();
         self.kill();
+        self.resize_population();
         self.remove_extinct_species();
         self.reproduce();
         self.mutate();
@@ -233,6 +768,105 @@ impl Neat {
         }
     }
 
+    /// Advance real-time evolution by one tick and return the client that was replaced, if any
+    ///
+    /// Every client ages by one tick. Every [`interval`](RealTime::interval) ticks, the client with the lowest
+    /// score divided by the size of its species is replaced, out of the clients that are at least
+    /// [`minimum_age`](RealTime::minimum_age) ticks old. Its offspring is bred from a species that is chosen
+    /// by its average fitness, and is put in the first compatible species.
+    ///
+    /// The offspring takes the place of the replaced client, so the handles of all clients stay the same.
+    /// Only the clients without a species are assigned to one, the existing species are kept.
+    /// Nothing but the ages changes if real-time evolution is disabled.
+    pub fn tick(&mut self) -> Option<Rc<RefCell<Client>>> {
+        for client in &self.clients {
+            let mut client = client.borrow_mut();
+            client.age = client.age.saturating_add(1);
+        }
+
+        let real_time = self.real_time.as_mut()?;
+        if !real_time.tick() {
+            return None;
+        }
+        let minimum_age = real_time.minimum_age;
+
+        self.update_novelty();
+        for client in self.clients.clone() {
+            if !client.borrow().has_species {
+                self.assign_species(&client);
+            }
+        }
+        self.rank_objectives();
+
+        let worst = self
+            .clients
+            .iter()
+            .filter(|client| client.borrow().age >= minimum_age)
+            .map(|client| {
+                let species_size = self
+                    .species
+                    .iter()
+                    .find(|species| species.contains(client))
+                    .map_or(1, Species::len);
+
+                (client, client.borrow().score() / species_size as f32)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(client, _)| Rc::clone(client))?;
+
+        for species in &mut self.species {
+            species.remove(&worst);
+            species.evaluate_fitness();
+        }
+        self.species.retain(|species| !species.is_empty());
+
+        let mut thread = rand::thread_rng();
+        let parents = self
+            .species
+            .choose_weighted(&mut thread, |species| species.average_fitness.max(0.0))
+            .ok()
+            .or_else(|| self.species.choose(&mut thread))
+            .cloned();
+
+        let mut genome = match parents {
+            Some(species) => species.breed(self),
+            None => self.empty_genome(),
+        };
+        genome.mutate(self);
+
+        worst.swap(&Client::new(genome));
+        worst.borrow_mut().generate_calculator();
+        self.assign_species(&worst);
+
+        Some(worst)
+    }
+
+    #[doc(hidden)]
+    /// Calculate the novelty of all clients and the score they are selected on
+    pub fn update_novelty(&mut self) {
+        let Some(novelty_search) = &mut self.novelty_search else {
+            for client in &self.clients {
+                client.borrow_mut().set_score(None);
+            }
+            return;
+        };
+
+        let behaviors: Vec<Vec<f32>> = self
+            .clients
+            .iter()
+            .map(|client| client.borrow().behavior.clone())
+            .collect();
+        let novelty = novelty_search.update(&behaviors);
+
+        for (client, novelty) in self.clients.iter().zip(novelty) {
+            let mut client = client.borrow_mut();
+            let score = novelty_search.score(client.fitness, novelty);
+
+            client.novelty = novelty;
+            client.set_score(Some(score));
+        }
+    }
+
     #[doc(hidden)]
     /// Generate new species
     pub fn gen_species(&mut self) {
@@ -240,26 +874,78 @@ impl Neat {
             species.reset();
         }
 
-        for client in &self.clients {
-            if client.borrow().has_species {
-                continue;
+        for client in self.clients.clone() {
+            if !client.borrow().has_species {
+                self.assign_species(&client);
             }
+        }
+    }
 
-            let mut found = false;
-            for species in &mut self.species {
-                if species.put(Rc::clone(client)) {
-                    found = true;
-                    break;
-                }
+    /// Put a client in the first compatible species, or in a new species if there is none
+    fn assign_species(&mut self, client: &Rc<RefCell<Client>>) {
+        for species in &mut self.species {
+            if species.put(Rc::clone(client)) {
+                return;
             }
+        }
 
-            if !found {
-                client.borrow_mut().has_species = true;
-                self.species.push(Species::new(Rc::clone(client)));
-            }
+        client.borrow_mut().has_species = true;
+        self.species.push(Species::new(Rc::clone(client)));
+    }
+
+    #[doc(hidden)]
+    /// Rank all clients on their objectives, if multi-objective evolution is enabled
+    ///
+    /// The whole population is ranked at once, so the scores of different species can be compared
+    /// when the offspring are divided between them.
+    pub fn rank_objectives(&mut self) {
+        if !self.multi_objective {
+            return;
+        }
+
+        let objectives: Vec<Vec<f32>> = self
+            .clients
+            .iter()
+            .map(|client| client.borrow().objectives.clone())
+            .collect();
+
+        for (client, score) in self.clients.iter().zip(pareto::scores(&objectives)) {
+            client.borrow_mut().set_score(Some(score));
         }
     }
 
+    #[doc(hidden)]
+    /// Gather the statistics of this generation and update the phase
+    pub fn update_statistics(&mut self) {
+        let mut best_fitness = f32::MIN;
+        let mut total_fitness = 0.0;
+        let mut total_complexity = 0;
+
+        for client in &self.clients {
+            let client = client.borrow();
+
+            best_fitness = best_fitness.max(client.fitness);
+            total_fitness += client.fitness;
+            total_complexity += client.genome.complexity();
+        }
+
+        let population_size = self.clients.len().max(1) as f32;
+        let mean_complexity = total_complexity as f32 / population_size;
+
+        if let Some(phased_search) = &mut self.phased_search {
+            phased_search.update(mean_complexity);
+        }
+
+        self.statistics = Statistics {
+            generation: self.statistics.generation + 1,
+            best_fitness,
+            average_fitness: total_fitness / population_size,
+            mean_complexity,
+            species: self.species.len(),
+            phase: self.phase(),
+        };
+    }
+
     #[doc(hidden)]
     /// Kill a certain percentage of species
     pub fn kill(&mut self) {
@@ -269,6 +955,33 @@ impl Neat {
         }
     }
 
+    #[doc(hidden)]
+    /// Cull the weakest clients or add new clients to be bred until the population has the right size
+    pub fn resize_population(&mut self) {
+        let population_size = self.population_size as usize;
+
+        if self.clients.len() > population_size {
+            let mut weakest = self.clients.clone();
+            weakest.sort_by(|a, b| a.borrow().score().total_cmp(&b.borrow().score()));
+            weakest.truncate(self.clients.len() - population_size);
+
+            for client in &weakest {
+                for species in &mut self.species {
+                    species.remove(client);
+                }
+            }
+
+            self.clients
+                .retain(|client| !weakest.iter().any(|weak| Rc::ptr_eq(weak, client)));
+        }
+
+        while self.clients.len() < population_size {
+            // New clients don't have a species, so they are bred in 'reproduce'
+            let client = Client::new(self.empty_genome());
+            self.clients.push(client);
+        }
+    }
+
     #[doc(hidden)]
     /// Remove all the extinct species
     pub fn remove_extinct_species(&mut self) {
@@ -283,15 +996,38 @@ impl Neat {
     #[doc(hidden)]
     /// Reproduce the clients
     pub fn reproduce(&mut self) {
+        // A shrinking population can leave no species to breed from, so start over from the initial genome
+        if self.species.is_empty() {
+            for client in self.clients.clone() {
+                let genome = self.initial_genome();
+                client.borrow_mut().genome = genome;
+                client.borrow_mut().age = 0;
+                self.assign_species(&client);
+            }
+        }
+
         let mut all_species = self.species.clone();
         let mut thread = rand::thread_rng();
+
+        // Parsimony can make the average fitness negative, so choose uniformly when no species has a positive one
+        let uniform = !all_species
+            .iter()
+            .any(|species| species.average_fitness > 0.0);
+
         for client in self.clients.clone() {
             if !client.borrow().has_species {
                 let species = all_species
-                    .choose_weighted_mut(&mut thread, |s| s.average_fitness)
+                    .choose_weighted_mut(&mut thread, |s| {
+                        if uniform {
+                            1.0
+                        } else {
+                            s.average_fitness.max(0.0)
+                        }
+                    })
                     .expect("Species is empty");
 
                 client.borrow_mut().genome = species.breed(self);
+                client.borrow_mut().age = 0;
                 species.force_put(Rc::clone(&client));
             }
         }
@@ -338,6 +1074,25 @@ impl Neat {
         best_client
     }
 
+    /// Returns the clients whose objectives aren't dominated by any other client
+    pub fn pareto_front(&self) -> Vec<Client> {
+        let objectives: Vec<Vec<f32>> = self
+            .clients
+            .iter()
+            .map(|client| client.borrow().objectives.clone())
+            .collect();
+
+        pareto::non_dominated_sort(&objectives)
+            .first()
+            .map(|front| {
+                front
+                    .iter()
+                    .map(|index| self.clients[*index].borrow().clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Print all the different species
     pub fn print_species(&self) {
         println!("#######################################################");
@@ -1,4 +1,4 @@
-use crate::genome::NodeGene;
+use crate::genome::{NodeGene, NodeType};
 
 use super::*;
 
@@ -126,3 +126,526 @@ fn get_connection() {
         // assert_ne!(connection2.hash(&mut hasher), connection3.hash(&mut hasher));
     }
 }
+
+#[test]
+fn statistics() {
+    Neat::test_config();
+    let mut neat = Neat::new(3, 3, 20);
+
+    assert_eq!(neat.statistics().generation, 0);
+
+    for (index, mut client) in neat.iter_clients().into_iter().enumerate() {
+        client.fitness = index as f32;
+    }
+
+    neat.gen_species();
+    neat.update_statistics();
+
+    let statistics = neat.statistics();
+
+    assert_eq!(statistics.generation, 1);
+    assert_eq!(statistics.best_fitness, 19.0);
+    assert_eq!(statistics.average_fitness, 9.5);
+    assert_eq!(statistics.mean_complexity, 7.0);
+    assert_eq!(statistics.species, 1);
+    assert_eq!(statistics.phase, Phase::Complexifying);
+}
+
+#[test]
+fn phased_search() {
+    Neat::test_config();
+    let mut neat = Neat::new(3, 3, 20);
+
+    neat.set_phased_search(Some(PhasedSearch::new(2.0, 3)));
+    neat.update_statistics();
+    assert_eq!(neat.phase(), Phase::Complexifying);
+
+    for client in neat.clients.clone() {
+        for _ in 0..3 {
+            client.borrow_mut().genome.mutate_link(&mut neat);
+        }
+    }
+
+    neat.update_statistics();
+
+    assert_eq!(neat.statistics().mean_complexity, 10.0);
+    assert_eq!(neat.statistics().phase, Phase::Simplifying);
+    assert_eq!(neat.phase(), Phase::Simplifying);
+}
+
+#[test]
+fn novelty_search() {
+    Neat::test_config();
+    let mut neat = Neat::new(3, 3, 4);
+
+    neat.set_novelty_search(Some(NoveltySearch::new(1, 5.0, 0.5)));
+
+    for (index, mut client) in neat.iter_clients().into_iter().enumerate() {
+        client.fitness = 2.0;
+        client.behavior = vec![[0.0, 1.0, 2.0, 10.0][index]];
+    }
+
+    neat.update_novelty();
+
+    let novelty: Vec<f32> = neat.clients.iter().map(|c| c.borrow().novelty).collect();
+    let scores: Vec<f32> = neat.clients.iter().map(|c| c.borrow().score()).collect();
+    assert_eq!(novelty, vec![1.0, 1.0, 1.0, 8.0]);
+    assert_eq!(scores, vec![1.5, 1.5, 1.5, 5.0]);
+    assert_eq!(
+        neat.novelty_search()
+            .expect("Novelty search is enabled")
+            .archive(),
+        &[vec![10.0]]
+    );
+
+    // Without novelty search the clients are selected on their fitness again
+    neat.set_novelty_search(None);
+    neat.update_novelty();
+
+    for client in &neat.clients {
+        assert_eq!(client.borrow().score(), 2.0);
+    }
+}
+
+#[test]
+fn pareto_front() {
+    Neat::test_config();
+    let mut neat = Neat::new(3, 3, 4);
+
+    let objectives = [[1.0, 0.0], [0.0, 1.0], [0.5, 0.5], [0.5, 0.0]];
+    for (mut client, objectives) in neat.iter_clients().into_iter().zip(objectives) {
+        client.objectives = objectives.to_vec();
+    }
+
+    let front: Vec<Vec<f32>> = neat
+        .pareto_front()
+        .into_iter()
+        .map(|client| client.objectives)
+        .collect();
+    assert_eq!(front, vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![0.5, 0.5]]);
+}
+
+#[test]
+fn rank_objectives_across_species() {
+    Neat::test_config();
+    let mut neat = Neat::new(3, 3, 4);
+    neat.set_multi_objective(true);
+
+    let objectives = [[2.0, 2.0], [0.0, 0.0], [1.0, 1.0], [0.5, 0.5]];
+    for (client, objectives) in neat.clients.iter().zip(objectives) {
+        client.borrow_mut().objectives = objectives.to_vec();
+    }
+
+    // The best and worst client in one species, the two in between in another
+    let mut first = Species::new(Rc::clone(&neat.clients[0]));
+    first.force_put(Rc::clone(&neat.clients[1]));
+    let mut second = Species::new(Rc::clone(&neat.clients[2]));
+    second.force_put(Rc::clone(&neat.clients[3]));
+    neat.species = vec![first, second];
+
+    neat.rank_objectives();
+
+    let scores: Vec<f32> = neat.clients.iter().map(|c| c.borrow().score()).collect();
+    assert_eq!(scores, vec![4.5, 1.5, 3.5, 2.5]);
+
+    for species in &mut neat.species {
+        species.evaluate_fitness();
+    }
+    assert_eq!(neat.species[0].average_fitness, 3.0);
+    assert_eq!(neat.species[1].average_fitness, 3.0);
+}
+
+#[test]
+fn initial_topology() {
+    Neat::test_config();
+    let mut neat = Neat::new(3, 3, 20);
+
+    for client in &neat.clients {
+        assert_eq!(client.borrow().genome.connections.len(), 0);
+    }
+
+    neat.set_initial_topology(InitialTopology::FullyConnected);
+
+    let first = neat.get_client(0).borrow().genome.clone();
+    assert_eq!(first.nodes.len(), 7);
+    assert_eq!(first.connections.len(), 12);
+
+    // Every client shares the same innovation numbers
+    for client in &neat.clients {
+        let genome = &client.borrow().genome;
+        for (connection, other) in genome.connections.data.iter().zip(&first.connections.data) {
+            assert_eq!(connection.innovation_number, other.innovation_number);
+        }
+    }
+
+    neat.set_initial_topology(InitialTopology::PartiallyConnected(0.0));
+    for client in &neat.clients {
+        assert_eq!(client.borrow().genome.connections.len(), 0);
+    }
+
+    neat.set_initial_topology(InitialTopology::PartiallyConnected(1.0));
+    for client in &neat.clients {
+        assert_eq!(client.borrow().genome.connections.len(), 12);
+    }
+
+    neat.set_initial_topology(InitialTopology::FullyConnectedHidden(2));
+    assert_eq!(neat.all_nodes.len(), 9);
+    assert_eq!(neat.all_connections.len(), 14);
+
+    for client in &neat.clients {
+        assert_eq!(client.borrow().genome.nodes.len(), 9);
+        assert_eq!(client.borrow().genome.connections.len(), 14);
+    }
+
+    let mut client = neat.get_client(0).borrow().clone();
+    assert_eq!(client.calculate(&vec![1.0, 0.5, 0.0]).len(), 3);
+}
+
+#[test]
+fn from_seed_genome() {
+    Neat::test_config();
+    let mut neat = Neat::new(3, 2, 20);
+
+    let mut genome = neat.empty_genome();
+    for _ in 0..5 {
+        genome.mutate_link(&mut neat);
+        genome.mutate_node(&mut neat);
+    }
+
+    // Create some nodes and connections that aren't in the seed
+    let mut other = neat.empty_genome();
+    for _ in 0..5 {
+        other.mutate_link(&mut neat);
+        other.mutate_node(&mut neat);
+    }
+
+    let highest_node = genome
+        .nodes
+        .data
+        .iter()
+        .map(|node| node.innovation_number)
+        .max()
+        .unwrap();
+    let highest_connection = genome
+        .connections
+        .data
+        .iter()
+        .map(|connection| connection.innovation_number)
+        .max()
+        .unwrap();
+
+    // With a single client the seed isn't mutated, so nothing new is registered
+    let mut seeded = Neat::from_seed_genome(&genome, 1);
+
+    assert_eq!(seeded.input_size, 3);
+    assert_eq!(seeded.output_size, 2);
+    assert_eq!(seeded.clients.len(), 1);
+    assert_eq!(seeded.all_nodes.len(), highest_node as usize);
+    assert_eq!(seeded.get_client(0).borrow().genome, genome);
+
+    for node in &genome.nodes.data {
+        assert_eq!(
+            seeded.get_node(node.innovation_number as usize),
+            Some(*node)
+        );
+    }
+
+    // Existing connections keep their innovation numbers
+    for connection in &genome.connections.data {
+        let registered = seeded.get_connection(connection.from, connection.to);
+        assert_eq!(registered.innovation_number, connection.innovation_number);
+    }
+
+    // New genes continue right after the ones of the seed
+    let from = seeded.create_node(0.2, 0.5);
+    let to = seeded.create_node(0.8, 0.5);
+    let connection = seeded.get_connection(from, to);
+
+    assert_eq!(connection.innovation_number, highest_connection + 1);
+    assert_eq!(from.innovation_number, highest_node + 1);
+
+    let empty = seeded.empty_genome();
+    assert_eq!(empty.nodes.len(), 6);
+
+    // The rest of a larger population are mutated copies
+    let mut seeded = Neat::from_seed_genome(&genome, 30);
+    assert_eq!(seeded.clients.len(), 30);
+    assert_eq!(seeded.get_client(0).borrow().genome, genome);
+
+    for _ in 0..5 {
+        seeded.set_fitness(&vec![1.0; 30]);
+        seeded.evolve();
+    }
+}
+
+#[test]
+fn add_remove_inputs_outputs() {
+    Neat::test_config();
+    let mut neat = Neat::new(3, 2, 20);
+    neat.set_initial_topology(InitialTopology::FullyConnected);
+
+    for client in neat.clients.clone() {
+        client.borrow_mut().genome.mutate_node(&mut neat);
+    }
+
+    let before = neat.get_client(0).borrow().genome.clone();
+    assert_eq!(before.nodes.len(), 7);
+    assert_eq!(before.connections.len(), 9);
+
+    neat.add_inputs(2);
+    neat.add_outputs(1);
+
+    assert_eq!(neat.input_size, 5);
+    assert_eq!(neat.output_size, 3);
+    assert_eq!(neat.empty_genome().nodes.len(), 9);
+
+    let after = neat.get_client(0).borrow().genome.clone();
+    assert_eq!(after.nodes.len(), 10);
+
+    // All evolved connections are kept with their innovation numbers
+    for connection in &before.connections.data {
+        let kept = after
+            .connections
+            .data
+            .iter()
+            .find(|other| other.innovation_number == connection.innovation_number)
+            .expect("Connection was removed");
+        assert_eq!(kept.weight, connection.weight);
+    }
+
+    // The new inputs come before the bias node
+    assert_eq!(
+        after.nodes.get(3).unwrap().innovation_number,
+        neat.input_nodes[3]
+    );
+    assert_eq!(
+        after.nodes.get(5).unwrap().innovation_number,
+        neat.bias_node
+    );
+    assert_eq!(neat.bias_node, 4);
+
+    let output = neat.get_client(0).borrow_mut().calculate(&vec![1.0; 5]);
+    assert_eq!(output.len(), 3);
+
+    neat.remove_input(0);
+    neat.remove_output(2);
+
+    assert_eq!(neat.input_size, 4);
+    assert_eq!(neat.output_size, 2);
+
+    for client in neat.clients.clone() {
+        let mut client = client.borrow_mut();
+
+        assert!(!client.genome.nodes.contains(&NodeGene::new(1)));
+        assert!(client
+            .genome
+            .connections
+            .data
+            .iter()
+            .all(|connection| connection.from.innovation_number != 1));
+
+        assert_eq!(client.calculate(&vec![1.0; 4]).len(), 2);
+    }
+
+    for _ in 0..5 {
+        neat.set_fitness(&vec![1.0; 20]);
+        neat.evolve();
+    }
+}
+
+#[test]
+fn remove_inputs_outputs_after_evolving() {
+    Neat::test_config();
+    let mut neat = Neat::new(3, 2, 20);
+    neat.set_initial_topology(InitialTopology::FullyConnected);
+
+    for _ in 0..5 {
+        // Change the genes in place like the weight, toggle and Hebbian mutations do
+        for client in neat.clients.clone() {
+            let genome = &mut client.borrow_mut().genome;
+            for _ in 0..10 {
+                genome.mutate_weight_shift();
+                genome.mutate_weight_random();
+                genome.mutate_link_toggle();
+                genome.mutate_hebbian();
+            }
+        }
+
+        neat.set_fitness(&vec![1.0; 20]);
+        neat.evolve();
+    }
+
+    let input = neat.input_nodes[0];
+    let output = neat.output_nodes[0];
+    neat.remove_input(0);
+    neat.remove_output(0);
+
+    for client in neat.clients.clone() {
+        let mut client = client.borrow_mut();
+
+        for connection in &client.genome.connections.data {
+            for innovation_number in [input, output] {
+                assert_ne!(connection.from.innovation_number, innovation_number);
+                assert_ne!(connection.to.innovation_number, innovation_number);
+            }
+        }
+
+        assert_eq!(client.calculate(&vec![1.0; 2]).len(), 1);
+    }
+
+    neat.set_fitness(&vec![1.0; 20]);
+    neat.evolve();
+}
+
+#[test]
+fn set_population_size() {
+    Neat::test_config();
+    let mut neat = Neat::new(3, 3, 20);
+
+    neat.set_population_size(40);
+    neat.set_fitness(&vec![1.0; 20]);
+    neat.evolve();
+
+    assert_eq!(neat.clients.len(), 40);
+    assert_eq!(neat.population_size, 40);
+
+    let fitnesses: Vec<f32> = (0..40).map(|fitness| fitness as f32).collect();
+    neat.set_fitness(&fitnesses);
+
+    neat.set_population_size(10);
+    neat.gen_species();
+    neat.kill();
+    neat.resize_population();
+
+    // Only the strongest clients are left, in their original order
+    assert_eq!(neat.clients.len(), 10);
+    for (index, client) in neat.clients.iter().enumerate() {
+        assert_eq!(client.borrow().fitness, (30 + index) as f32);
+    }
+
+    neat.remove_extinct_species();
+    neat.reproduce();
+    neat.mutate();
+
+    assert_eq!(neat.clients.len(), 10);
+}
+
+#[test]
+fn shrink_population_to_one() {
+    Neat::test_config();
+    let mut neat = Neat::new(3, 3, 20);
+
+    // The only species left is too small to survive, so the population starts over
+    neat.set_population_size(1);
+    neat.set_fitness(&vec![1.0; 20]);
+    neat.evolve();
+
+    assert_eq!(neat.clients.len(), 1);
+    assert_eq!(neat.species.len(), 1);
+    assert!(neat.clients[0].borrow().has_species);
+
+    neat.set_fitness(&vec![1.0]);
+    neat.evolve();
+    assert_eq!(neat.clients.len(), 1);
+}
+
+#[test]
+fn node_types() {
+    Neat::test_config();
+    let mut neat = Neat::new(3, 2, 15);
+
+    let types: Vec<NodeType> = neat.all_nodes.iter().map(|node| node.node_type).collect();
+    assert_eq!(
+        types,
+        vec![
+            NodeType::Input,
+            NodeType::Input,
+            NodeType::Input,
+            NodeType::Bias,
+            NodeType::Output,
+            NodeType::Output,
+        ]
+    );
+
+    assert_eq!(neat.create_node(0.1, 0.5).node_type, NodeType::Hidden);
+
+    neat.add_inputs(1);
+    neat.add_outputs(1);
+
+    let genome = neat.empty_genome();
+    assert_eq!(genome.nodes.data[3].node_type, NodeType::Input);
+    assert_eq!(genome.nodes.data[4].node_type, NodeType::Bias);
+    assert_eq!(genome.nodes.data[7].node_type, NodeType::Output);
+}
+
+#[test]
+fn real_time() {
+    Neat::test_config();
+    let mut neat = Neat::new(2, 1, 10);
+
+    let handles: Vec<_> = (0..10).map(|index| neat.get_client(index)).collect();
+    let fitnesses: Vec<f32> = (1..=10).map(|fitness| fitness as f32).collect();
+    neat.set_fitness(&fitnesses);
+
+    // Without real-time evolution the clients only age
+    assert!(neat.tick().is_none());
+    assert_eq!(handles[0].borrow().age, 1);
+
+    neat.set_real_time(Some(RealTime::new(3, 5)));
+
+    // The third tick is a replacement, but every client is too young
+    for _ in 0..5 {
+        assert!(neat.tick().is_none());
+    }
+
+    let replaced = neat.tick().expect("No client was replaced");
+    assert!(Rc::ptr_eq(&replaced, &handles[0]));
+    assert_eq!(replaced.borrow().age, 0);
+    assert_eq!(replaced.borrow().fitness, 0.0);
+    assert!(neat
+        .species
+        .iter()
+        .any(|species| species.contains(&replaced)));
+
+    // The other clients keep their place and fitness
+    assert_eq!(neat.clients.len(), 10);
+    for (index, handle) in handles.iter().enumerate().skip(1) {
+        assert!(Rc::ptr_eq(handle, &neat.get_client(index)));
+        assert_eq!(handle.borrow().fitness, (index + 1) as f32);
+        assert_eq!(handle.borrow().age, 7);
+    }
+
+    // The new client is too young, so the next worst client is replaced
+    neat.tick();
+    neat.tick();
+    let replaced = neat.tick().expect("No client was replaced");
+    assert!(Rc::ptr_eq(&replaced, &handles[1]));
+    assert_eq!(handles[0].borrow().age, 3);
+
+    assert_eq!(neat.real_time().map(RealTime::ticks), Some(9));
+}
+
+#[test]
+fn simplifying_deletes() {
+    Neat::test_config();
+    let mut neat = Neat::new(3, 3, 1);
+
+    let mut genome = neat.empty_genome();
+    for _ in 0..50 {
+        genome.mutate_link(&mut neat);
+    }
+    let connections = genome.connections.len();
+
+    // Deleting is made certain, while adding stays unlikely
+    let mut phased_search = PhasedSearch::new(0.0, 10);
+    phased_search.deletion_multiplier = 1000.0;
+    phased_search.update(0.0);
+    phased_search.update(1.0);
+    neat.set_phased_search(Some(phased_search));
+    assert_eq!(neat.phase(), Phase::Simplifying);
+
+    for _ in 0..5 {
+        genome.mutate(&mut neat);
+    }
+    assert_eq!(genome.connections.len(), connections - 5);
+}
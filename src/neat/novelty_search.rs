@@ -0,0 +1,127 @@
+/// Rewards clients for behaving differently from the rest of the population and the archive
+///
+/// Every client gets a [behavior](super::Client::behavior) and its novelty is the average distance
+/// to the `k` nearest behaviors of the other clients and the archive. Behaviors that are more novel
+/// than the archive threshold are added to the archive, so the search keeps moving away from them.
+///
+/// # Examples
+///
+/// ```rust
+/// use profqu_neat::neat::NoveltySearch;
+///
+/// let mut novelty_search = NoveltySearch::new(1, 2.0, 0.0);
+///
+/// let novelty = novelty_search.update(&[vec![0.0], vec![1.0], vec![5.0]]);
+/// assert_eq!(novelty, vec![1.0, 1.0, 4.0]);
+///
+/// // Only the last behavior was novel enough
+/// assert_eq!(novelty_search.archive(), &[vec![5.0]]);
+///
+/// // It's now less novel to be close to the archive
+/// let novelty = novelty_search.update(&[vec![0.0], vec![6.0]]);
+/// assert_eq!(novelty, vec![5.0, 1.0]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct NoveltySearch {
+    /// The number of nearest neighbors the novelty is averaged over
+    pub k: usize,
+    /// The novelty a behavior needs to be added to the archive
+    pub archive_threshold: f32,
+    /// How much the fitness counts in selection, 0.0 only uses the novelty and 1.0 only the fitness
+    pub fitness_weight: f32,
+    archive: Vec<Vec<f32>>,
+}
+
+impl NoveltySearch {
+    /// Create a new novelty search with an empty archive
+    pub fn new(k: usize, archive_threshold: f32, fitness_weight: f32) -> Self {
+        Self {
+            k,
+            archive_threshold,
+            fitness_weight,
+            archive: Vec::new(),
+        }
+    }
+
+    /// Get the behaviors that were novel enough to be archived
+    pub fn archive(&self) -> &[Vec<f32>] {
+        &self.archive
+    }
+
+    /// Calculate the novelty of every behavior and archive the ones above the threshold
+    pub fn update(&mut self, behaviors: &[Vec<f32>]) -> Vec<f32> {
+        let novelty: Vec<f32> = behaviors
+            .iter()
+            .enumerate()
+            .map(|(index, behavior)| {
+                let mut distances: Vec<f32> = behaviors
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| *other != index)
+                    .map(|(_, other)| other)
+                    .chain(&self.archive)
+                    .map(|other| Self::distance(behavior, other))
+                    .collect();
+
+                if distances.is_empty() || self.k == 0 {
+                    return 0.0;
+                }
+
+                distances.sort_by(f32::total_cmp);
+                distances.truncate(self.k);
+
+                distances.iter().sum::<f32>() / distances.len() as f32
+            })
+            .collect();
+
+        for (behavior, novelty) in behaviors.iter().zip(&novelty) {
+            if *novelty > self.archive_threshold {
+                self.archive.push(behavior.clone());
+            }
+        }
+
+        novelty
+    }
+
+    /// Blend the novelty with the fitness into the score that is used in selection
+    pub fn score(&self, fitness: f32, novelty: f32) -> f32 {
+        self.fitness_weight * fitness + (1.0 - self.fitness_weight) * novelty
+    }
+
+    /// The euclidean distance between two behaviors
+    fn distance(behavior: &[f32], other: &[f32]) -> f32 {
+        behavior
+            .iter()
+            .zip(other)
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<f32>()
+            .sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update() {
+        let mut novelty_search = NoveltySearch::new(2, 10.0, 0.5);
+
+        let novelty = novelty_search.update(&[vec![0.0, 0.0], vec![3.0, 4.0], vec![0.0, 1.0]]);
+        let diagonal = 18.0_f32.sqrt();
+        assert_eq!(
+            novelty,
+            vec![3.0, 0.5 * (diagonal + 5.0), 0.5 * (1.0 + diagonal)]
+        );
+        assert!(novelty_search.archive().is_empty());
+
+        // Without other behaviors there is nothing to be novel against
+        assert_eq!(novelty_search.update(&[vec![1.0, 1.0]]), vec![0.0]);
+
+        novelty_search.archive_threshold = 0.5;
+        novelty_search.update(&[vec![0.0, 0.0], vec![0.0, 1.0]]);
+        assert_eq!(novelty_search.archive().len(), 2);
+
+        assert_eq!(novelty_search.score(4.0, 2.0), 3.0);
+    }
+}
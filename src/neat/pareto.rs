@@ -0,0 +1,163 @@
+//! Ranking of clients with multiple objectives, like in NSGA-II
+//!
+//! All objectives are maximized, just like the fitness.
+
+/// Check if `a` is at least as good as `b` in every objective and better in at least one
+pub(crate) fn dominates(a: &[f32], b: &[f32]) -> bool {
+    let mut better = false;
+
+    for (a, b) in a.iter().zip(b) {
+        if a < b {
+            return false;
+        }
+        better |= a > b;
+    }
+
+    better
+}
+
+/// Sort the indices of the objectives into fronts, the first front isn't dominated by anything
+pub(crate) fn non_dominated_sort(objectives: &[Vec<f32>]) -> Vec<Vec<usize>> {
+    let mut dominated_by = vec![0; objectives.len()];
+    let mut dominating = vec![Vec::new(); objectives.len()];
+
+    for a in 0..objectives.len() {
+        for b in 0..objectives.len() {
+            if dominates(&objectives[a], &objectives[b]) {
+                dominating[a].push(b);
+                dominated_by[b] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+    let mut front: Vec<usize> = (0..objectives.len())
+        .filter(|index| dominated_by[*index] == 0)
+        .collect();
+
+    while !front.is_empty() {
+        let mut next = Vec::new();
+        for a in &front {
+            for b in &dominating[*a] {
+                dominated_by[*b] -= 1;
+                if dominated_by[*b] == 0 {
+                    next.push(*b);
+                }
+            }
+        }
+
+        fronts.push(front);
+        front = next;
+    }
+
+    fronts
+}
+
+/// Calculate the crowding distance of every index in a front, the ones at the edges are infinite
+pub(crate) fn crowding_distance(objectives: &[Vec<f32>], front: &[usize]) -> Vec<f32> {
+    let mut distances = vec![0.0; front.len()];
+    let objective_count = front.first().map_or(0, |index| objectives[*index].len());
+
+    // The values of every objective for the clients in the front
+    let columns = (0..objective_count).map(|objective| {
+        front
+            .iter()
+            .map(|index| objectives[*index][objective])
+            .collect::<Vec<f32>>()
+    });
+
+    for values in columns {
+        let value = |position: usize| values[position];
+
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|a, b| value(*a).total_cmp(&value(*b)));
+
+        let (first, last) = (order[0], order[order.len() - 1]);
+        distances[first] = f32::INFINITY;
+        distances[last] = f32::INFINITY;
+
+        let range = value(last) - value(first);
+        if range <= 0.0 {
+            continue;
+        }
+
+        for window in order.windows(3) {
+            distances[window[1]] += (value(window[2]) - value(window[0])) / range;
+        }
+    }
+
+    distances
+}
+
+/// Score every objective by Pareto front and crowding distance
+///
+/// Every front scores at least one higher than the fronts it dominates,
+/// inside a front the less crowded clients score up to a half higher.
+pub(crate) fn scores(objectives: &[Vec<f32>]) -> Vec<f32> {
+    let mut scores = vec![0.0; objectives.len()];
+    let fronts = non_dominated_sort(objectives);
+
+    for (rank, front) in fronts.iter().enumerate() {
+        let distances = crowding_distance(objectives, front);
+
+        for (index, distance) in front.iter().zip(distances) {
+            let crowding = if distance.is_infinite() {
+                0.5
+            } else {
+                0.5 * distance / (1.0 + distance)
+            };
+
+            scores[*index] = (fronts.len() - rank) as f32 + crowding;
+        }
+    }
+
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dominate() {
+        assert!(dominates(&[1.0, 2.0], &[1.0, 1.0]));
+        assert!(!dominates(&[1.0, 1.0], &[1.0, 1.0]));
+        assert!(!dominates(&[2.0, 0.0], &[1.0, 1.0]));
+    }
+
+    #[test]
+    fn sort_fronts() {
+        let objectives = vec![
+            vec![1.0, 1.0],
+            vec![3.0, 0.0],
+            vec![2.0, 2.0],
+            vec![0.0, 3.0],
+            vec![0.0, 0.0],
+        ];
+
+        let fronts = non_dominated_sort(&objectives);
+        assert_eq!(fronts, vec![vec![1, 2, 3], vec![0], vec![4]]);
+
+        let distances = crowding_distance(&objectives, &fronts[0]);
+        assert_eq!(distances, vec![f32::INFINITY, 2.0, f32::INFINITY]);
+
+        assert!(non_dominated_sort(&[]).is_empty());
+    }
+
+    #[test]
+    fn score_fronts() {
+        let objectives = vec![
+            vec![1.0, 1.0],
+            vec![3.0, 0.0],
+            vec![2.0, 2.0],
+            vec![0.0, 3.0],
+            vec![0.0, 0.0],
+        ];
+
+        assert_eq!(
+            scores(&objectives),
+            vec![2.5, 3.5, 3.0 + 0.5 * 2.0 / 3.0, 3.5, 1.5]
+        );
+        assert!(scores(&[]).is_empty());
+    }
+}
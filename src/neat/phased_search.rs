@@ -0,0 +1,132 @@
+/// The phase a phased search is currently in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Phase {
+    /// New links and nodes are added with the configured probabilities
+    #[default]
+    Complexifying,
+    /// Links and nodes are deleted instead of added
+    Simplifying,
+}
+
+/// Alternates between complexifying and simplifying the population
+/// based on the mean complexity of all genomes
+///
+/// The ceiling that starts a simplifying phase isn't absolute, it's the
+/// [`complexity_margin`](PhasedSearch::complexity_margin) above the floor, which is the lowest mean complexity
+/// the last simplifying phase reached, or the first mean complexity. While simplifying, links and nodes are deleted
+/// with the probabilities of adding them multiplied by the [`deletion_multiplier`](PhasedSearch::deletion_multiplier),
+/// so deletions dominate.
+///
+/// # Examples
+///
+/// ```rust
+/// use profqu_neat::neat::{Phase, PhasedSearch};
+///
+/// let mut phased_search = PhasedSearch::new(10.0, 2);
+///
+/// phased_search.update(5.0);
+/// assert_eq!(phased_search.phase(), Phase::Complexifying);
+///
+/// phased_search.update(16.0);
+/// assert_eq!(phased_search.phase(), Phase::Simplifying);
+///
+/// phased_search.update(12.0);
+/// phased_search.update(12.0);
+/// phased_search.update(12.0);
+/// assert_eq!(phased_search.phase(), Phase::Complexifying);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct PhasedSearch {
+    /// How far the mean complexity may rise above the floor before simplifying
+    pub complexity_margin: f32,
+    /// The number of generations without a new lowest mean complexity before complexifying again
+    pub stagnation_limit: u32,
+    /// How many times more likely deleting a link or node is while simplifying than adding it while complexifying
+    pub deletion_multiplier: f32,
+    phase: Phase,
+    floor: Option<f32>,
+    lowest: f32,
+    stagnation: u32,
+}
+
+impl PhasedSearch {
+    /// Create a new phased search that starts out complexifying, with a deletion multiplier of 10.0
+    pub fn new(complexity_margin: f32, stagnation_limit: u32) -> Self {
+        Self {
+            complexity_margin,
+            stagnation_limit,
+            deletion_multiplier: 10.0,
+            phase: Phase::Complexifying,
+            floor: None,
+            lowest: f32::MAX,
+            stagnation: 0,
+        }
+    }
+
+    /// Get the current phase
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Update the phase with the mean complexity of the current generation
+    pub fn update(&mut self, mean_complexity: f32) {
+        match self.phase {
+            Phase::Complexifying => {
+                let floor = *self.floor.get_or_insert(mean_complexity);
+
+                if mean_complexity > floor + self.complexity_margin {
+                    self.phase = Phase::Simplifying;
+                    self.lowest = mean_complexity;
+                    self.stagnation = 0;
+                }
+            }
+            Phase::Simplifying => {
+                if mean_complexity < self.lowest {
+                    self.lowest = mean_complexity;
+                    self.stagnation = 0;
+                } else {
+                    self.stagnation += 1;
+                }
+
+                if self.stagnation >= self.stagnation_limit {
+                    self.phase = Phase::Complexifying;
+                    self.floor = Some(self.lowest);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn switch_phases() {
+        let mut phased_search = PhasedSearch::new(5.0, 2);
+
+        phased_search.update(10.0);
+        assert_eq!(phased_search.phase(), Phase::Complexifying);
+
+        phased_search.update(15.0);
+        assert_eq!(phased_search.phase(), Phase::Complexifying);
+
+        phased_search.update(15.5);
+        assert_eq!(phased_search.phase(), Phase::Simplifying);
+
+        phased_search.update(13.0);
+        phased_search.update(12.0);
+        phased_search.update(12.0);
+        assert_eq!(phased_search.phase(), Phase::Simplifying);
+
+        phased_search.update(12.5);
+        assert_eq!(phased_search.phase(), Phase::Complexifying);
+
+        // The new floor is the lowest complexity reached while simplifying
+        phased_search.update(17.0);
+        assert_eq!(phased_search.phase(), Phase::Complexifying);
+
+        phased_search.update(17.5);
+        assert_eq!(phased_search.phase(), Phase::Simplifying);
+    }
+}
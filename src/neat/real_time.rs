@@ -0,0 +1,58 @@
+/// The settings of real-time evolution, where clients are replaced one at a time with [`Neat::tick`](super::Neat::tick)
+///
+/// # Examples
+///
+/// ```rust
+/// use profqu_neat::neat::RealTime;
+///
+/// let mut real_time = RealTime::new(3, 10);
+///
+/// assert!(!real_time.tick());
+/// assert!(!real_time.tick());
+/// assert!(real_time.tick());
+/// assert_eq!(real_time.ticks(), 3);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct RealTime {
+    /// The number of ticks between two replacements
+    pub interval: u32,
+    /// The number of ticks a client has to live before it can be replaced
+    pub minimum_age: u32,
+    ticks: u32,
+}
+
+impl RealTime {
+    /// Create new settings for real-time evolution
+    pub fn new(interval: u32, minimum_age: u32) -> Self {
+        Self {
+            interval,
+            minimum_age,
+            ticks: 0,
+        }
+    }
+
+    /// Get the number of ticks so far, which wraps around after `u32::MAX`
+    pub fn ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    /// Count a tick and return if a client should be replaced
+    pub fn tick(&mut self) -> bool {
+        self.ticks = self.ticks.wrapping_add(1);
+        self.ticks % self.interval.max(1) == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tick_wraps() {
+        let mut real_time = RealTime::new(1, 0);
+        real_time.ticks = u32::MAX;
+
+        assert!(real_time.tick());
+        assert_eq!(real_time.ticks(), 0);
+    }
+}
@@ -1,17 +1,20 @@
-use std::{cell::RefCell, fmt::Debug, rc::Rc};
+use std::{cell::RefCell, cmp::Ordering, fmt::Debug, rc::Rc};
 
 use rand::seq::SliceRandom;
 
-use crate::{genome::Genome, Neat};
+use crate::{
+    genome::{Genome, NodeType},
+    Neat,
+};
 
-use super::{Client, Config};
+use super::{Client, Config, Parsimony};
 
 #[derive(Clone)]
 /// The species that stores all clients and a represantive
 pub struct Species {
     clients: Vec<Rc<RefCell<Client>>>,
     representative: Rc<RefCell<Client>>,
-    /// The average fitness of this species
+    /// The average [score](Client::score) of this species, which is the fitness without novelty search
     pub average_fitness: f32,
 }
 
@@ -54,6 +57,29 @@ impl Species {
         self.clients.push(client);
     }
 
+    /// Check if a client is in this species
+    pub fn contains(&self, client: &Rc<RefCell<Client>>) -> bool {
+        self.clients.iter().any(|c| Rc::ptr_eq(c, client))
+    }
+
+    /// Remove a client from this species and return if it was in it
+    ///
+    /// If it was the representative, another client becomes the representative.
+    pub fn remove(&mut self, client: &Rc<RefCell<Client>>) -> bool {
+        if let Some(index) = self.clients.iter().position(|c| Rc::ptr_eq(c, client)) {
+            self.clients.remove(index);
+
+            if Rc::ptr_eq(&self.representative, client) {
+                if let Some(representative) = self.clients.first() {
+                    self.representative = Rc::clone(representative);
+                }
+            }
+            true
+        } else {
+            false
+        }
+    }
+
     /// Make this species go extinct
     pub fn go_extinct(&mut self) {
         for client in &self.clients {
@@ -61,9 +87,55 @@ impl Species {
         }
     }
 
+    /// Penalty for the enabled connections and hidden nodes of a client's genome
+    fn complexity_penalty(config: &Config, client: &Client) -> f32 {
+        let connections = client
+            .genome
+            .connections
+            .data
+            .iter()
+            .filter(|connection| connection.enabled)
+            .count();
+        let hidden_nodes = client
+            .genome
+            .nodes
+            .data
+            .iter()
+            .filter(|node| node.node_type == NodeType::Hidden)
+            .count();
+
+        connections as f32 * config.parsimony_connection_penalty
+            + hidden_nodes as f32 * config.parsimony_node_penalty
+    }
+
+    /// The score of a client, with the complexity subtracted for linear parsimony
+    fn adjusted_fitness(config: &Config, client: &Client) -> f32 {
+        match config.parsimony {
+            Parsimony::Linear => client.score() - Self::complexity_penalty(config, client),
+            Parsimony::None | Parsimony::Lexicographic => client.score(),
+        }
+    }
+
+    /// Compare two clients on their adjusted fitness, lexicographic parsimony prefers the simpler one on a tie
+    fn compare(config: &Config, a: &Client, b: &Client) -> Ordering {
+        let ordering =
+            Self::adjusted_fitness(config, a).total_cmp(&Self::adjusted_fitness(config, b));
+
+        match config.parsimony {
+            Parsimony::Lexicographic => ordering.then_with(|| {
+                Self::complexity_penalty(config, b).total_cmp(&Self::complexity_penalty(config, a))
+            }),
+            Parsimony::None | Parsimony::Linear => ordering,
+        }
+    }
+
     /// Calculate a new average fitness for this species
     pub fn evaluate_fitness(&mut self) {
-        let total_fitness: f32 = self.clients.iter().map(|x| x.borrow().fitness).sum();
+        let total_fitness: f32 = self
+            .clients
+            .iter()
+            .map(|x| Self::adjusted_fitness(Config::global(), &x.borrow()))
+            .sum();
 
         self.average_fitness = total_fitness / self.clients.len() as f32;
     }
@@ -84,9 +156,9 @@ impl Species {
 
     /// Kill 50% of this species
     pub fn kill(&mut self, percentage: f32) {
-        // Sort so that the lowest fitness is at index 0
+        // Sort so that the lowest score is at index 0
         self.clients
-            .sort_by(|a, b| a.borrow().fitness.total_cmp(&b.borrow().fitness));
+            .sort_by(|a, b| Self::compare(Config::global(), &a.borrow(), &b.borrow()));
 
         let kill_num = (percentage * self.clients.len() as f32) as usize;
         self.clients.drain(0..kill_num);
@@ -97,7 +169,9 @@ impl Species {
         let client1 = self.get_random_element();
         let client2 = self.get_random_element();
 
-        if client1.borrow().fitness > client2.borrow().fitness {
+        if Self::compare(Config::global(), &client1.borrow(), &client2.borrow())
+            == Ordering::Greater
+        {
             Genome::crossover(neat, &client1.borrow().genome, &client2.borrow().genome)
         } else {
             Genome::crossover(neat, &client2.borrow().genome, &client1.borrow().genome)
@@ -211,4 +285,64 @@ mod tests {
 
         assert_eq!(species.len(), 11);
     }
+
+    #[test]
+    fn parsimony() {
+        Neat::test_config();
+        let mut neat = Neat::new(2, 1, 10);
+
+        let simple = Client::new(neat.empty_genome());
+        let complex = Client::new(neat.empty_genome());
+        complex.borrow_mut().genome.add_connection(&mut neat, 0, 3);
+        complex.borrow_mut().genome.mutate_node(&mut neat);
+
+        simple.borrow_mut().fitness = 2.0;
+        complex.borrow_mut().fitness = 2.0;
+        let (simple, complex) = (simple.borrow(), complex.borrow());
+
+        let mut config = Config::init_zero();
+        config.parsimony_connection_penalty = 0.25;
+        config.parsimony_node_penalty = 1.0;
+        assert_eq!(Species::complexity_penalty(&config, &complex), 1.5);
+
+        assert_eq!(
+            Species::compare(&config, &simple, &complex),
+            Ordering::Equal
+        );
+
+        config.parsimony = Parsimony::Linear;
+        assert_eq!(Species::adjusted_fitness(&config, &complex), 0.5);
+        assert_eq!(
+            Species::compare(&config, &simple, &complex),
+            Ordering::Greater
+        );
+
+        config.parsimony = Parsimony::Lexicographic;
+        assert_eq!(Species::adjusted_fitness(&config, &complex), 2.0);
+        assert_eq!(
+            Species::compare(&config, &simple, &complex),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn remove() {
+        Neat::test_config();
+        let mut neat = Neat::new(3, 3, 100);
+
+        let representative = Client::new(neat.empty_genome());
+        let client = Client::new(neat.empty_genome());
+
+        let mut species = Species::new(Rc::clone(&representative));
+        species.force_put(Rc::clone(&client));
+        assert!(species.contains(&representative));
+
+        // The other client takes over as the representative
+        assert!(species.remove(&representative));
+        assert!(!species.contains(&representative));
+        assert!(Rc::ptr_eq(&species.representative, &client));
+
+        assert!(species.remove(&client));
+        assert!(species.is_empty());
+    }
 }
@@ -0,0 +1,18 @@
+use super::Phase;
+
+/// The statistics of a single generation, gathered in [`Neat::evolve`](crate::Neat::evolve)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Statistics {
+    /// The number of the generation these statistics are from
+    pub generation: u32,
+    /// The highest fitness of all clients
+    pub best_fitness: f32,
+    /// The average fitness of all clients
+    pub average_fitness: f32,
+    /// The average number of nodes and connections of all genomes
+    pub mean_complexity: f32,
+    /// The number of species
+    pub species: usize,
+    /// The phase the search is in after this generation
+    pub phase: Phase,
+}
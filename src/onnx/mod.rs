@@ -0,0 +1,295 @@
+//! Export genomes as [ONNX](https://onnx.ai) models, enabled with the `onnx` feature
+//!
+//! Every node becomes a small group of operators: its incoming values are concatenated and
+//! multiplied with a weight matrix using `MatMul`, followed by a `Sigmoid` or `Relu` operator
+//! depending on the configured activation function.
+//! The model has a single float input named `inputs` with shape `[N]`
+//! and a single float output named `outputs` with shape `[M]`.
+
+mod model;
+mod protobuf;
+
+pub use model::{Graph, Model, Operator, Tensor, TensorData, ValueInfo};
+
+use std::collections::HashSet;
+
+use crate::genome::{Genome, NodeGene};
+use crate::neat::{ActivationFunction, Config};
+
+/// The version of the ONNX intermediate representation that is written
+const IR_VERSION: i64 = 7;
+/// The version of the default operator set that is used
+const OPSET_VERSION: i64 = 13;
+
+impl Genome {
+    /// Convert this genome into an ONNX model
+    ///
+    /// Disabled connections, connections that form a cycle and nodes that can't reach any output are left out.
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 10);
+    ///
+    /// let mut genome = neat.empty_genome();
+    /// genome.add_connection(&mut neat, 0, 3);
+    ///
+    /// let model = genome.to_onnx_model();
+    /// assert_eq!(model.graph.inputs[0].shape, vec![2]);
+    /// assert_eq!(model.graph.outputs[0].shape, vec![1]);
+    /// ```
+    pub fn to_onnx_model(&self) -> Model {
+        let (input_nodes, bias, hidden_nodes, output_nodes) = self.evaluation_order();
+        let useful = self.nodes_reaching(&output_nodes);
+        let recurrent = self.recurrent_connections();
+
+        let mut graph = Graph {
+            name: "genome".to_string(),
+            inputs: vec![ValueInfo {
+                name: "inputs".to_string(),
+                shape: vec![input_nodes.len() as i64],
+            }],
+            outputs: vec![ValueInfo {
+                name: "outputs".to_string(),
+                shape: vec![output_nodes.len() as i64],
+            }],
+            ..Graph::default()
+        };
+
+        for (index, node) in input_nodes.iter().enumerate() {
+            let name = format!("node_{}", node.innovation_number);
+
+            graph.initializers.push(Tensor {
+                name: format!("{name}_index"),
+                dims: vec![1],
+                data: TensorData::Int64(vec![index as i64]),
+            });
+            graph.operators.push(Operator {
+                name: format!("{name}_gather"),
+                op_type: "Gather".to_string(),
+                inputs: vec!["inputs".to_string(), format!("{name}_index")],
+                outputs: vec![name],
+                attributes: vec![("axis".to_string(), 0)],
+            });
+        }
+
+        if let Some(bias) = bias {
+            graph.initializers.push(Tensor {
+                name: format!("node_{}", bias.innovation_number),
+                dims: vec![1],
+                data: TensorData::Float(vec![1.0]),
+            });
+        }
+
+        let activation = match Config::global().activation {
+            ActivationFunction::Sigmoid => "Sigmoid",
+            ActivationFunction::Relu => "Relu",
+        };
+
+        for node in hidden_nodes
+            .iter()
+            .filter(|node| useful.contains(&node.innovation_number))
+            .chain(&output_nodes)
+        {
+            self.add_onnx_node(&mut graph, *node, activation, &recurrent);
+        }
+
+        graph.operators.push(Operator {
+            name: "outputs_concat".to_string(),
+            op_type: "Concat".to_string(),
+            inputs: output_nodes
+                .iter()
+                .map(|node| format!("node_{}", node.innovation_number))
+                .collect(),
+            outputs: vec!["outputs".to_string()],
+            attributes: vec![("axis".to_string(), 0)],
+        });
+
+        Model {
+            ir_version: IR_VERSION,
+            producer_name: env!("CARGO_PKG_NAME").to_string(),
+            opset_version: OPSET_VERSION,
+            graph,
+        }
+    }
+
+    /// Add the operators that calculate the output of 'node' to 'graph'
+    fn add_onnx_node(
+        &self,
+        graph: &mut Graph,
+        node: NodeGene,
+        activation: &str,
+        recurrent: &HashSet<u32>,
+    ) {
+        let name = format!("node_{}", node.innovation_number);
+
+        let (sources, weights): (Vec<String>, Vec<f32>) = self
+            .connections
+            .data
+            .iter()
+            .filter(|connection| {
+                connection.enabled
+                    && connection.to == node
+                    && !recurrent.contains(&connection.innovation_number)
+            })
+            .map(|connection| {
+                (
+                    format!("node_{}", connection.from.innovation_number),
+                    connection.weight,
+                )
+            })
+            .unzip();
+
+        if sources.is_empty() {
+            graph.initializers.push(Tensor {
+                name: format!("{name}_sum"),
+                dims: vec![1],
+                data: TensorData::Float(vec![0.0]),
+            });
+        } else {
+            graph.initializers.push(Tensor {
+                name: format!("{name}_weights"),
+                dims: vec![weights.len() as i64, 1],
+                data: TensorData::Float(weights),
+            });
+            graph.operators.push(Operator {
+                name: format!("{name}_concat"),
+                op_type: "Concat".to_string(),
+                inputs: sources,
+                outputs: vec![format!("{name}_inputs")],
+                attributes: vec![("axis".to_string(), 0)],
+            });
+            graph.operators.push(Operator {
+                name: format!("{name}_matmul"),
+                op_type: "MatMul".to_string(),
+                inputs: vec![format!("{name}_inputs"), format!("{name}_weights")],
+                outputs: vec![format!("{name}_sum")],
+                attributes: Vec::new(),
+            });
+        }
+
+        graph.operators.push(Operator {
+            name: format!("{name}_activation"),
+            op_type: activation.to_string(),
+            inputs: vec![format!("{name}_sum")],
+            outputs: vec![name],
+            attributes: Vec::new(),
+        });
+    }
+
+    /// Convert this genome into the bytes of an `.onnx` file
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    /// use profqu_neat::onnx::Model;
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 10);
+    ///
+    /// let genome = neat.empty_genome();
+    /// let bytes = genome.to_onnx();
+    ///
+    /// assert_eq!(Model::decode(&bytes), Ok(genome.to_onnx_model()));
+    /// ```
+    pub fn to_onnx(&self) -> Vec<u8> {
+        self.to_onnx_model().encode()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{calculations::Calculator, Neat};
+
+    /// Evaluate a model with the few operators that genomes are exported with
+    fn evaluate(model: &Model, inputs: &[f32]) -> Vec<f32> {
+        let mut floats: HashMap<String, Vec<f32>> = HashMap::new();
+        let mut ints: HashMap<String, Vec<i64>> = HashMap::new();
+
+        floats.insert("inputs".to_string(), inputs.to_vec());
+        for tensor in &model.graph.initializers {
+            match &tensor.data {
+                TensorData::Float(data) => {
+                    floats.insert(tensor.name.clone(), data.clone());
+                }
+                TensorData::Int64(data) => {
+                    ints.insert(tensor.name.clone(), data.clone());
+                }
+            }
+        }
+
+        for operator in &model.graph.operators {
+            let input = |index: usize| floats[&operator.inputs[index]].clone();
+
+            let output = match operator.op_type.as_str() {
+                "Gather" => {
+                    let indices = &ints[&operator.inputs[1]];
+                    indices
+                        .iter()
+                        .map(|index| input(0)[*index as usize])
+                        .collect()
+                }
+                "Concat" => (0..operator.inputs.len()).flat_map(input).collect(),
+                "MatMul" => vec![input(0).iter().zip(input(1)).map(|(a, b)| a * b).sum()],
+                "Sigmoid" => input(0).iter().map(|x| 1.0 / (1.0 + (-x).exp())).collect(),
+                "Relu" => input(0).iter().map(|x| x.max(0.0)).collect(),
+                op_type => panic!("Unsupported operator {op_type}"),
+            };
+
+            floats.insert(operator.outputs[0].clone(), output);
+        }
+
+        floats["outputs"].clone()
+    }
+
+    #[test]
+    fn round_trip() {
+        Neat::test_config();
+        let mut neat = Neat::new(3, 2, 10);
+
+        let mut genome = neat.empty_genome();
+        genome.add_connection(&mut neat, 0, 4);
+        genome.mutate_node(&mut neat);
+        genome.add_connection(&mut neat, 1, 6);
+        genome.add_connection(&mut neat, 3, 5);
+        genome.add_connection(&mut neat, 6, 5);
+        genome.add_connection(&mut neat, 2, 5);
+
+        let weights = [0.75, -1.5, 0.25, 2.0, -0.125, 1.25];
+        for (connection, weight) in genome.connections.data.iter_mut().zip(weights) {
+            connection.weight = weight;
+        }
+        genome.connections.data[5].enabled = false;
+
+        let model = genome.to_onnx_model();
+        let bytes = genome.to_onnx();
+        let decoded = Model::decode(&bytes).unwrap();
+
+        assert_eq!(decoded, model);
+        assert_eq!(decoded.ir_version, IR_VERSION);
+        assert_eq!(decoded.opset_version, OPSET_VERSION);
+        assert_eq!(decoded.producer_name, "profqu_neat");
+
+        // 3 gathers, 3 operators for the hidden node and each output, and the final concat
+        assert_eq!(decoded.graph.operators.len(), 3 + 3 * 3 + 1);
+
+        let mut calculator = Calculator::new(genome);
+        for inputs in [[0.0, 0.0, 0.0], [1.0, 0.5, -0.5], [-2.0, 3.0, 1.0]] {
+            let expected = calculator.calculate(&inputs.to_vec()).unwrap();
+            let result = evaluate(&decoded, &inputs);
+
+            assert_eq!(result.len(), expected.len());
+            for (result, expected) in result.iter().zip(expected) {
+                assert!((result - expected).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert!(Model::decode(&[0x3a, 0x10, 0x0a]).is_err());
+    }
+}
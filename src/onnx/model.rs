@@ -0,0 +1,328 @@
+use super::protobuf::{Reader, Writer};
+
+/// The `FLOAT` element type of an ONNX tensor
+pub const FLOAT: i64 = 1;
+/// The `INT64` element type of an ONNX tensor
+pub const INT64: i64 = 7;
+/// The `INT` type of an ONNX attribute
+const ATTRIBUTE_INT: i64 = 2;
+
+/// An ONNX model, the `ModelProto` message
+#[derive(Clone, Debug, PartialEq)]
+pub struct Model {
+    /// The version of the ONNX intermediate representation
+    pub ir_version: i64,
+    /// The name of the program that created this model
+    pub producer_name: String,
+    /// The version of the default operator set
+    pub opset_version: i64,
+    /// The graph that calculates the outputs
+    pub graph: Graph,
+}
+
+/// An ONNX graph, the `GraphProto` message
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Graph {
+    /// The name of this graph
+    pub name: String,
+    /// All the operators, in topological order
+    pub operators: Vec<Operator>,
+    /// All the constant tensors
+    pub initializers: Vec<Tensor>,
+    /// The inputs of this graph
+    pub inputs: Vec<ValueInfo>,
+    /// The outputs of this graph
+    pub outputs: Vec<ValueInfo>,
+}
+
+/// An operator in an ONNX graph, the `NodeProto` message
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Operator {
+    /// The name of this operator
+    pub name: String,
+    /// The type of this operator, like `MatMul`
+    pub op_type: String,
+    /// The names of the input tensors
+    pub inputs: Vec<String>,
+    /// The names of the output tensors
+    pub outputs: Vec<String>,
+    /// The integer attributes of this operator
+    pub attributes: Vec<(String, i64)>,
+}
+
+/// The data of a tensor
+#[derive(Clone, Debug, PartialEq)]
+pub enum TensorData {
+    /// Float data
+    Float(Vec<f32>),
+    /// Integer data
+    Int64(Vec<i64>),
+}
+
+/// A constant tensor, the `TensorProto` message
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tensor {
+    /// The name of this tensor
+    pub name: String,
+    /// The dimensions of this tensor
+    pub dims: Vec<i64>,
+    /// The data of this tensor
+    pub data: TensorData,
+}
+
+/// The name and shape of a float tensor, the `ValueInfoProto` message
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValueInfo {
+    /// The name of the tensor
+    pub name: String,
+    /// The dimensions of the tensor
+    pub shape: Vec<i64>,
+}
+
+impl Model {
+    /// Encode this model in the protobuf format of `.onnx` files
+    pub fn encode(&self) -> Vec<u8> {
+        let mut writer = Writer::new();
+
+        writer.int64(1, self.ir_version);
+        writer.string(2, &self.producer_name);
+        writer.message(7, |writer| self.graph.encode(writer));
+        writer.message(8, |writer| {
+            writer.string(1, "");
+            writer.int64(2, self.opset_version);
+        });
+
+        writer.finish()
+    }
+
+    /// Decode a model from the protobuf format of `.onnx` files
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the bytes aren't a valid model
+    pub fn decode(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut model = Self {
+            ir_version: 0,
+            producer_name: String::new(),
+            opset_version: 0,
+            graph: Graph::default(),
+        };
+
+        let mut reader = Reader::new(bytes);
+        while !reader.is_empty() {
+            match reader.field()? {
+                (1, value) => model.ir_version = value.int64()?,
+                (2, value) => model.producer_name = value.string()?,
+                (7, value) => model.graph = Graph::decode(value.bytes()?)?,
+                (8, value) => {
+                    let mut reader = Reader::new(value.bytes()?);
+                    while !reader.is_empty() {
+                        if let (2, value) = reader.field()? {
+                            model.opset_version = value.int64()?;
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(model)
+    }
+}
+
+impl Graph {
+    fn encode(&self, writer: &mut Writer) {
+        for operator in &self.operators {
+            writer.message(1, |writer| operator.encode(writer));
+        }
+        writer.string(2, &self.name);
+        for initializer in &self.initializers {
+            writer.message(5, |writer| initializer.encode(writer));
+        }
+        for input in &self.inputs {
+            writer.message(11, |writer| input.encode(writer));
+        }
+        for output in &self.outputs {
+            writer.message(12, |writer| output.encode(writer));
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut graph = Self::default();
+
+        let mut reader = Reader::new(bytes);
+        while !reader.is_empty() {
+            match reader.field()? {
+                (1, value) => graph.operators.push(Operator::decode(value.bytes()?)?),
+                (2, value) => graph.name = value.string()?,
+                (5, value) => graph.initializers.push(Tensor::decode(value.bytes()?)?),
+                (11, value) => graph.inputs.push(ValueInfo::decode(value.bytes()?)?),
+                (12, value) => graph.outputs.push(ValueInfo::decode(value.bytes()?)?),
+                _ => (),
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+impl Operator {
+    fn encode(&self, writer: &mut Writer) {
+        for input in &self.inputs {
+            writer.string(1, input);
+        }
+        for output in &self.outputs {
+            writer.string(2, output);
+        }
+        writer.string(3, &self.name);
+        writer.string(4, &self.op_type);
+        for (name, value) in &self.attributes {
+            writer.message(5, |writer| {
+                writer.string(1, name);
+                writer.int64(3, *value);
+                writer.int64(20, ATTRIBUTE_INT);
+            });
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut operator = Self::default();
+
+        let mut reader = Reader::new(bytes);
+        while !reader.is_empty() {
+            match reader.field()? {
+                (1, value) => operator.inputs.push(value.string()?),
+                (2, value) => operator.outputs.push(value.string()?),
+                (3, value) => operator.name = value.string()?,
+                (4, value) => operator.op_type = value.string()?,
+                (5, value) => {
+                    let mut name = String::new();
+                    let mut attribute = 0;
+
+                    let mut reader = Reader::new(value.bytes()?);
+                    while !reader.is_empty() {
+                        match reader.field()? {
+                            (1, value) => name = value.string()?,
+                            (3, value) => attribute = value.int64()?,
+                            (20, value) if value.int64()? != ATTRIBUTE_INT => {
+                                return Err("Only integer attributes are supported");
+                            }
+                            _ => (),
+                        }
+                    }
+
+                    operator.attributes.push((name, attribute));
+                }
+                _ => (),
+            }
+        }
+
+        Ok(operator)
+    }
+}
+
+impl Tensor {
+    fn encode(&self, writer: &mut Writer) {
+        writer.packed_int64s(1, &self.dims);
+        match &self.data {
+            TensorData::Float(data) => {
+                writer.int64(2, FLOAT);
+                writer.packed_floats(4, data);
+            }
+            TensorData::Int64(data) => {
+                writer.int64(2, INT64);
+                writer.packed_int64s(7, data);
+            }
+        }
+        writer.string(8, &self.name);
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut name = String::new();
+        let mut dims = Vec::new();
+        let mut data_type = 0;
+        let mut floats = Vec::new();
+        let mut ints = Vec::new();
+
+        let mut reader = Reader::new(bytes);
+        while !reader.is_empty() {
+            match reader.field()? {
+                (1, value) => dims.extend(value.int64s()?),
+                (2, value) => data_type = value.int64()?,
+                (4, value) => floats.extend(value.floats()?),
+                (7, value) => ints.extend(value.int64s()?),
+                (8, value) => name = value.string()?,
+                _ => (),
+            }
+        }
+
+        let data = match data_type {
+            FLOAT => TensorData::Float(floats),
+            INT64 => TensorData::Int64(ints),
+            _ => return Err("Only float and int64 tensors are supported"),
+        };
+
+        Ok(Self { name, dims, data })
+    }
+}
+
+impl ValueInfo {
+    fn encode(&self, writer: &mut Writer) {
+        writer.string(1, &self.name);
+        // TypeProto -> TypeProto.Tensor -> TensorShapeProto -> Dimension
+        writer.message(2, |writer| {
+            writer.message(1, |writer| {
+                writer.int64(1, FLOAT);
+                writer.message(2, |writer| {
+                    for dim in &self.shape {
+                        writer.message(1, |writer| writer.int64(1, *dim));
+                    }
+                });
+            });
+        });
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut name = String::new();
+        let mut shape = Vec::new();
+
+        let mut reader = Reader::new(bytes);
+        while !reader.is_empty() {
+            match reader.field()? {
+                (1, value) => name = value.string()?,
+                (2, value) => {
+                    let tensor_type = Self::nested_field(value.bytes()?, 1)?;
+                    let tensor_shape = Self::nested_field(tensor_type, 2)?;
+
+                    let mut reader = Reader::new(tensor_shape);
+                    while !reader.is_empty() {
+                        if let (1, dimension) = reader.field()? {
+                            let mut reader = Reader::new(dimension.bytes()?);
+                            while !reader.is_empty() {
+                                if let (1, dim_value) = reader.field()? {
+                                    shape.push(dim_value.int64()?);
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Self { name, shape })
+    }
+
+    /// Get the bytes of a nested message field
+    fn nested_field(bytes: &[u8], field: u32) -> Result<&[u8], &'static str> {
+        let mut reader = Reader::new(bytes);
+        while !reader.is_empty() {
+            let (number, value) = reader.field()?;
+            if number == field {
+                return value.bytes();
+            }
+        }
+
+        Err("Missing field in value info")
+    }
+}
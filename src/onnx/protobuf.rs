@@ -0,0 +1,258 @@
+//! Just enough of the protobuf wire format to write and read ONNX models
+
+/// The wire type of a varint field
+pub const VARINT: u8 = 0;
+/// The wire type of a length delimited field
+pub const LENGTH_DELIMITED: u8 = 2;
+/// The wire type of a 32 bit field
+pub const FIXED32: u8 = 5;
+
+/// A writer for protobuf messages
+#[derive(Default)]
+pub struct Writer {
+    buffer: Vec<u8>,
+}
+
+impl Writer {
+    /// Create a new empty writer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the bytes that were written
+    pub fn finish(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buffer.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.buffer.push(value as u8);
+    }
+
+    fn key(&mut self, field: u32, wire_type: u8) {
+        self.varint(u64::from(field) << 3 | u64::from(wire_type));
+    }
+
+    /// Write an integer field
+    pub fn int64(&mut self, field: u32, value: i64) {
+        self.key(field, VARINT);
+        self.varint(value as u64);
+    }
+
+    /// Write a bytes field
+    pub fn bytes(&mut self, field: u32, value: &[u8]) {
+        self.key(field, LENGTH_DELIMITED);
+        self.varint(value.len() as u64);
+        self.buffer.extend_from_slice(value);
+    }
+
+    /// Write a string field
+    pub fn string(&mut self, field: u32, value: &str) {
+        self.bytes(field, value.as_bytes());
+    }
+
+    /// Write a nested message field
+    pub fn message(&mut self, field: u32, write: impl FnOnce(&mut Writer)) {
+        let mut writer = Writer::new();
+        write(&mut writer);
+        self.bytes(field, &writer.finish());
+    }
+
+    /// Write a packed repeated float field
+    pub fn packed_floats(&mut self, field: u32, values: &[f32]) {
+        let bytes: Vec<u8> = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        self.bytes(field, &bytes);
+    }
+
+    /// Write a packed repeated integer field
+    pub fn packed_int64s(&mut self, field: u32, values: &[i64]) {
+        let mut writer = Writer::new();
+        for value in values {
+            writer.varint(*value as u64);
+        }
+        self.bytes(field, &writer.finish());
+    }
+}
+
+/// The value of a single field in a protobuf message
+#[derive(Debug, Clone, Copy)]
+pub enum Value<'a> {
+    /// A varint
+    Varint(u64),
+    /// A 32 bit value
+    Fixed32(u32),
+    /// A length delimited value: a string, bytes, a message or a packed field
+    Bytes(&'a [u8]),
+}
+
+impl<'a> Value<'a> {
+    /// Read this value as an integer
+    pub fn int64(self) -> Result<i64, &'static str> {
+        match self {
+            Value::Varint(value) => Ok(value as i64),
+            _ => Err("Expected a varint"),
+        }
+    }
+
+    /// Read this value as a byte slice
+    pub fn bytes(self) -> Result<&'a [u8], &'static str> {
+        match self {
+            Value::Bytes(bytes) => Ok(bytes),
+            _ => Err("Expected a length delimited value"),
+        }
+    }
+
+    /// Read this value as a string
+    pub fn string(self) -> Result<String, &'static str> {
+        String::from_utf8(self.bytes()?.to_vec()).map_err(|_| "Invalid UTF-8 in string")
+    }
+
+    /// Read this value as one or more floats, packed or not
+    pub fn floats(self) -> Result<Vec<f32>, &'static str> {
+        match self {
+            Value::Fixed32(bits) => Ok(vec![f32::from_bits(bits)]),
+            Value::Bytes(bytes) if bytes.len() % 4 == 0 => Ok(bytes
+                .chunks_exact(4)
+                .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .collect()),
+            _ => Err("Expected floats"),
+        }
+    }
+
+    /// Read this value as one or more integers, packed or not
+    pub fn int64s(self) -> Result<Vec<i64>, &'static str> {
+        match self {
+            Value::Varint(value) => Ok(vec![value as i64]),
+            Value::Bytes(bytes) => {
+                let mut reader = Reader::new(bytes);
+                let mut values = Vec::new();
+                while !reader.is_empty() {
+                    values.push(reader.varint()? as i64);
+                }
+                Ok(values)
+            }
+            Value::Fixed32(_) => Err("Expected integers"),
+        }
+    }
+}
+
+/// A reader for the fields of a protobuf message
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Create a new reader over the bytes of a message
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Check if all fields have been read
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], &'static str> {
+        if length > self.bytes.len() {
+            return Err("Unexpected end of message");
+        }
+
+        let (taken, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn varint(&mut self) -> Result<u64, &'static str> {
+        let mut value = 0;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= u64::from(byte & 0x7f) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err("Varint is too long")
+    }
+
+    /// Read the next field number and its value
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the message is malformed
+    pub fn field(&mut self) -> Result<(u32, Value<'a>), &'static str> {
+        let key = self.varint()?;
+        let field = u32::try_from(key >> 3).map_err(|_| "Field number is too large")?;
+
+        let value = match (key & 0x7) as u8 {
+            VARINT => Value::Varint(self.varint()?),
+            LENGTH_DELIMITED => {
+                let length = usize::try_from(self.varint()?).map_err(|_| "Length is too large")?;
+                Value::Bytes(self.take(length)?)
+            }
+            FIXED32 => {
+                let bytes = self.take(4)?;
+                Value::Fixed32(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            }
+            _ => return Err("Unsupported wire type"),
+        };
+
+        Ok((field, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut writer = Writer::new();
+        writer.int64(1, 300);
+        writer.int64(2, -1);
+        writer.string(3, "neat");
+        writer.packed_floats(4, &[1.5, -0.25]);
+        writer.packed_int64s(5, &[0, 127, 128, 1 << 40]);
+        writer.message(6, |writer| writer.int64(1, 7));
+
+        let bytes = writer.finish();
+        // Field 1 with value 300 is encoded as the key 0x08 followed by two bytes
+        assert_eq!(&bytes[..3], &[0x08, 0xac, 0x02]);
+
+        let mut reader = Reader::new(&bytes);
+
+        let (field, value) = reader.field().unwrap();
+        assert_eq!((field, value.int64().unwrap()), (1, 300));
+
+        let (field, value) = reader.field().unwrap();
+        assert_eq!((field, value.int64().unwrap()), (2, -1));
+
+        let (field, value) = reader.field().unwrap();
+        assert_eq!((field, value.string().unwrap()), (3, "neat".to_string()));
+
+        let (field, value) = reader.field().unwrap();
+        assert_eq!((field, value.floats().unwrap()), (4, vec![1.5, -0.25]));
+
+        let (field, value) = reader.field().unwrap();
+        assert_eq!(
+            (field, value.int64s().unwrap()),
+            (5, vec![0, 127, 128, 1 << 40])
+        );
+
+        let (field, value) = reader.field().unwrap();
+        assert_eq!(field, 6);
+        let mut nested = Reader::new(value.bytes().unwrap());
+        assert_eq!(nested.field().unwrap().1.int64().unwrap(), 7);
+
+        assert!(reader.is_empty());
+        assert!(Reader::new(&[0x0a, 0x05, 0x00]).field().is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use super::inference::{
+    to_fixed, to_float, QuantizedNetwork, ONE, TABLE_SIZE, TABLE_START, TABLE_STEP_BITS,
+};
+
+use crate::calculations::Calculator;
+use crate::genome::Genome;
+use crate::inference::Layout;
+use crate::neat::{ActivationFunction, Config};
+
+/// The largest shift that is used for the weights
+const MAX_WEIGHT_SHIFT: u32 = 14;
+
+impl QuantizedNetwork {
+    /// Create a quantized network that calculates the same outputs as `genome`
+    ///
+    /// The genome is [pruned](Genome::pruned) first and the activation function is taken from the config.
+    ///
+    /// ```rust
+    /// use profqu_neat::Neat;
+    /// use profqu_neat::quantized::{to_fixed, to_float, QuantizedNetwork};
+    ///
+    /// Neat::test_config();
+    /// let mut neat = Neat::new(2, 1, 10);
+    ///
+    /// let mut genome = neat.empty_genome();
+    /// genome.add_connection(&mut neat, 0, 3);
+    ///
+    /// let network = QuantizedNetwork::from_genome(&genome);
+    /// let outputs = network.calculate(&[to_fixed(1.0), to_fixed(0.0)]).unwrap();
+    /// assert!((to_float(outputs[0]) - 0.731_058_6).abs() < 0.001);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the genome has more than `u16::MAX` nodes
+    pub fn from_genome(genome: &Genome) -> Self {
+        let layout = Layout::from_genome(genome);
+
+        let largest_weight = layout
+            .weights
+            .iter()
+            .map(|weight| weight.abs())
+            .fold(0.0, f32::max);
+        let weight_shift = if largest_weight > 0.0 {
+            (f32::from(i16::MAX) / largest_weight)
+                .log2()
+                .floor()
+                .clamp(0.0, MAX_WEIGHT_SHIFT as f32) as u32
+        } else {
+            MAX_WEIGHT_SHIFT
+        };
+
+        let lookup_table = if let ActivationFunction::Sigmoid = Config::global().activation {
+            (0..TABLE_SIZE)
+                .map(|entry| {
+                    let input =
+                        (TABLE_START + ((entry as i32) << TABLE_STEP_BITS)) as f32 / f32::from(ONE);
+                    to_fixed(1.0 / (1.0 + (-input).exp()))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            weight_shift,
+            layout: layout.map_weights(|weight| {
+                (weight * (1 << weight_shift) as f32)
+                    .round()
+                    .clamp(f32::from(i16::MIN), f32::from(i16::MAX)) as i16
+            }),
+            lookup_table,
+        }
+    }
+
+    /// Get the largest difference between the outputs of this network and a [`Calculator`] of `genome` for all `inputs`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the number of inputs aren't equal to the number of input nodes.
+    pub fn max_error(&self, genome: &Genome, inputs: &[Vec<f32>]) -> Result<f32, &'static str> {
+        let mut calculator = Calculator::new(genome.clone());
+        let mut max_error: f32 = 0.0;
+
+        for inputs in inputs {
+            let expected = calculator.calculate(inputs)?;
+            let fixed_inputs: Vec<i16> = inputs.iter().map(|input| to_fixed(*input)).collect();
+            let outputs = self.calculate(&fixed_inputs)?;
+
+            for (expected, output) in expected.iter().zip(outputs) {
+                max_error = max_error.max((expected - to_float(output)).abs());
+            }
+        }
+
+        Ok(max_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::Neat;
+
+    #[test]
+    fn from_genome() {
+        Neat::test_config();
+        let mut neat = Neat::new(3, 2, 10);
+
+        let mut genome = neat.empty_genome();
+
+        let network = QuantizedNetwork::from_genome(&genome);
+        assert_eq!(network.input_size(), 3);
+        assert_eq!(network.output_size(), 2);
+        assert_eq!(network.calculate(&[0, 0, 0]), Ok(vec![ONE / 2, ONE / 2]));
+        assert!(network.calculate(&[0, 0]).is_err());
+
+        genome.add_connection(&mut neat, 0, 4);
+        genome.add_connection(&mut neat, 3, 4);
+        genome.connections.data[0].weight = 1.5;
+        genome.connections.data[1].weight = -0.25;
+
+        // 32767 / 1.5 is between 2^14 and 2^15
+        let network = QuantizedNetwork::from_genome(&genome);
+        assert_eq!(network.weight_shift, 14);
+        assert_eq!(network.layout.weights, vec![24576, -4096]);
+        assert_eq!(network.layout.sources, vec![0, 3]);
+    }
+
+    #[test]
+    fn max_error() {
+        Neat::test_config();
+        let mut neat = Neat::new(4, 3, 10);
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..20 {
+            let mut genome = neat.empty_genome();
+            for _ in 0..30 {
+                genome.mutate_link(&mut neat);
+                genome.mutate_node(&mut neat);
+                genome.mutate_weight_random();
+            }
+
+            let inputs: Vec<Vec<f32>> = (0..50)
+                .map(|_| (0..4).map(|_| rng.gen_range(-1.0..1.0)).collect())
+                .collect();
+
+            let network = QuantizedNetwork::from_genome(&genome);
+            let error = network.max_error(&genome, &inputs).unwrap();
+            assert!(error < 0.01, "error too large: {error}");
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Evaluating a [`QuantizedNetwork`] only needs `core` and `alloc`, so it can run on devices without an FPU
+
+use alloc::vec::Vec;
+
+use crate::inference::Layout;
+
+/// The number of fractional bits of the fixed-point values
+pub const FRACTION_BITS: u32 = 12;
+/// 1.0 as a fixed-point value
+pub const ONE: i16 = 1 << FRACTION_BITS;
+
+/// The number of fixed-point steps between two entries of the lookup table, as a power of 2
+pub(crate) const TABLE_STEP_BITS: u32 = 8;
+/// The number of entries in the lookup table
+pub(crate) const TABLE_SIZE: usize = 257;
+/// The input of the first entry in the lookup table, -8.0
+pub(crate) const TABLE_START: i32 = -8 * ONE as i32;
+
+/// A network with fixed-point weights and values, for devices without floating point hardware
+///
+/// Values are `i16`s with [`FRACTION_BITS`] fractional bits, so [`ONE`] is 1.0.
+/// The weights are `i16`s scaled by `2^weight_shift`, where the shift is chosen per network
+/// so the largest weight still fits. The sigmoid is calculated with a lookup table.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct QuantizedNetwork {
+    pub(super) weight_shift: u32,
+    /// The same layout as a [`Network`](crate::inference::Network), with the scaled weights
+    pub(super) layout: Layout<i16>,
+    /// The sigmoid from -8.0 to 8.0, empty for the relu
+    pub(super) lookup_table: Vec<i16>,
+}
+
+impl QuantizedNetwork {
+    /// Calculate the fixed-point outputs of this network for fixed-point inputs
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the number of inputs aren't equal to the number of input nodes.
+    pub fn calculate(&self, inputs: &[i16]) -> Result<Vec<i16>, &'static str> {
+        self.layout.calculate(
+            inputs,
+            ONE,
+            0,
+            |sum: i32, weight, value| sum.saturating_add(i32::from(weight) * i32::from(value)),
+            |sum| self.activate(self.unscale(sum)),
+        )
+    }
+
+    /// Get the number of inputs of this network
+    pub fn input_size(&self) -> usize {
+        self.layout.input_size
+    }
+
+    /// Get the number of outputs of this network
+    pub fn output_size(&self) -> usize {
+        self.layout.outputs.len()
+    }
+
+    /// Remove the weight scale from a sum, rounding to the nearest value
+    fn unscale(&self, sum: i32) -> i32 {
+        if self.weight_shift == 0 {
+            sum
+        } else {
+            sum.saturating_add(1 << (self.weight_shift - 1)) >> self.weight_shift
+        }
+    }
+
+    /// Apply the activation function to a fixed-point sum
+    fn activate(&self, sum: i32) -> i16 {
+        if self.lookup_table.is_empty() {
+            return sum.clamp(0, i32::from(i16::MAX)) as i16;
+        }
+
+        let position = (sum - TABLE_START).clamp(0, ((TABLE_SIZE - 1) as i32) << TABLE_STEP_BITS);
+        let index = (position >> TABLE_STEP_BITS) as usize;
+        let fraction = position & ((1 << TABLE_STEP_BITS) - 1);
+
+        let low = i32::from(self.lookup_table[index]);
+        let high = i32::from(self.lookup_table[(index + 1).min(TABLE_SIZE - 1)]);
+
+        (low + (((high - low) * fraction) >> TABLE_STEP_BITS)) as i16
+    }
+}
+
+/// Convert a float to a fixed-point value, saturating at the bounds
+pub fn to_fixed(value: f32) -> i16 {
+    let scaled = value * f32::from(ONE);
+    let rounding = if scaled < 0.0 { -0.5 } else { 0.5 };
+    (scaled + rounding) as i16
+}
+
+/// Convert a fixed-point value to a float
+pub fn to_float(value: i16) -> f32 {
+    f32::from(value) / f32::from(ONE)
+}
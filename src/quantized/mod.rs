@@ -0,0 +1,11 @@
+//! Quantized fixed-point networks for microcontrollers without floating point hardware
+//!
+//! A [`QuantizedNetwork`] is created from a genome with `QuantizedNetwork::from_genome`
+//! and `QuantizedNetwork::max_error` compares it to the float `Calculator`, both need the `std` feature.
+//! Calculating its outputs only uses integer math and doesn't need `std`.
+
+#[cfg(feature = "std")]
+mod conversion;
+mod inference;
+
+pub use inference::{to_fixed, to_float, QuantizedNetwork, FRACTION_BITS, ONE};
@@ -0,0 +1,18 @@
+use profqu_neat::Neat;
+
+#[test]
+fn linear_parsimony_with_low_fitness() {
+    Neat::load_config_from_file("tests/parsimony_config.txt");
+    let mut neat = Neat::new(3, 1, 50);
+
+    // The complexity penalty makes the adjusted fitness of every species negative
+    for _iteration in 0..10 {
+        for mut client in neat.iter_clients() {
+            client.fitness = 0.0;
+        }
+
+        neat.evolve();
+    }
+
+    assert_eq!(neat.iter_clients().len(), 50);
+}
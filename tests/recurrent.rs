@@ -0,0 +1,79 @@
+use profqu_neat::{genome::Genome, Neat};
+
+/// Check if some enabled or disabled connection of 'genome' is part of a cycle
+fn has_cycle(genome: &Genome) -> bool {
+    genome.connections.data.iter().any(|start| {
+        let mut reached = vec![start.to.innovation_number];
+        let mut index = 0;
+
+        while index < reached.len() {
+            if reached[index] == start.from.innovation_number {
+                return true;
+            }
+
+            for connection in &genome.connections.data {
+                if connection.from.innovation_number == reached[index]
+                    && !reached.contains(&connection.to.innovation_number)
+                {
+                    reached.push(connection.to.innovation_number);
+                }
+            }
+            index += 1;
+        }
+
+        false
+    })
+}
+
+#[test]
+fn ctrnn_evolves_cycles() {
+    Neat::load_config_from_file("tests/recurrent_config.txt");
+    let mut neat = Neat::new(2, 1, 50);
+
+    for _iteration in 0..20 {
+        for mut client in neat.iter_clients() {
+            let mut fitness = 0.0;
+            for step in 0..10 {
+                fitness += client.advance(&vec![step as f32, 1.0], 0.1, 5)[0];
+            }
+
+            // The feed-forward calculator leaves the cycles out instead of failing
+            client.calculate(&vec![1.0, 1.0]);
+
+            client.fitness = fitness;
+            client.reset_state();
+        }
+
+        neat.evolve();
+    }
+
+    assert!(neat
+        .iter_clients()
+        .iter()
+        .any(|client| has_cycle(&client.genome)));
+}
+
+#[test]
+fn spiking_network_evolves_cycles() {
+    Neat::load_config_from_file("tests/recurrent_config.txt");
+    let mut neat = Neat::new(2, 1, 50);
+
+    for _iteration in 0..20 {
+        for mut client in neat.iter_clients() {
+            let network = client.spiking_network();
+            for _ in 0..100 {
+                network.step(&vec![1.0, 0.5], 0.5).unwrap();
+            }
+
+            client.fitness = client.spiking_network().firing_rates()[0];
+            client.reset_state();
+        }
+
+        neat.evolve();
+    }
+
+    assert!(neat
+        .iter_clients()
+        .iter()
+        .any(|client| has_cycle(&client.genome)));
+}
@@ -0,0 +1,61 @@
+use profqu_neat::{calculations::Calculator, genome::Genome, Neat};
+
+mod network {
+    include!("rust_source/network.rs");
+}
+
+/// Create a genome with a hidden node, a disabled connection and a node that can't reach any output
+fn create_genome() -> Genome {
+    let mut neat = Neat::new(3, 2, 1);
+    let mut genome = neat.empty_genome();
+
+    // Nodes: inputs 0, 1 and 2, bias 3, outputs 4 and 5
+    genome.add_connection(&mut neat, 0, 4);
+    genome.mutate_node(&mut neat);
+
+    // The hidden node is at index 6
+    genome.add_connection(&mut neat, 1, 6);
+    genome.add_connection(&mut neat, 3, 6);
+    genome.add_connection(&mut neat, 3, 5);
+    genome.add_connection(&mut neat, 6, 5);
+    genome.add_connection(&mut neat, 2, 5);
+
+    let dead = neat.create_node(0.7, 0.2);
+    genome.nodes.add(dead);
+    genome.add_connection(&mut neat, 1, 7);
+
+    let weights = [0.75, -1.5, 0.25, -0.5, 2.0, -0.125, 1.25, 3.0];
+    for (connection, weight) in genome.connections.data.iter_mut().zip(weights) {
+        connection.weight = weight;
+    }
+
+    // Disable the connection from input 2 to output 5
+    genome.connections.data[6].enabled = false;
+
+    genome
+}
+
+#[test]
+fn to_rust_source() {
+    Neat::test_config();
+    let genome = create_genome();
+
+    let source = genome.to_rust_source("network");
+    assert_eq!(source, include_str!("rust_source/network.rs"));
+
+    let mut calculator = Calculator::new(genome);
+
+    for inputs in [
+        [0.0, 0.0, 0.0],
+        [1.0, 0.5, -0.5],
+        [-2.0, 3.0, 1.0],
+        [0.25, -0.75, 10.0],
+    ] {
+        let expected = calculator.calculate(&inputs.to_vec()).unwrap();
+        let result = network::network(inputs);
+
+        for (result, expected) in result.iter().zip(expected) {
+            assert!((result - expected).abs() < 1e-6);
+        }
+    }
+}
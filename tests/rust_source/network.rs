@@ -0,0 +1,10 @@
+pub fn network(inputs: [f32; 3]) -> [f32; 2] {
+    fn activation(x: f32) -> f32 {
+        1.0 / (1.0 + (-x).exp())
+    }
+
+    let node_7 = activation(0.75 * inputs[0] + 0.25 * inputs[1] - 0.5);
+    let node_5 = activation(-1.5 * node_7);
+    let node_6 = activation(2.0 - 0.125 * node_7);
+    [node_5, node_6]
+}